@@ -0,0 +1,63 @@
+use crate::game::{msg, Game};
+use crate::stage::achievements::ACHIEVEMENTS;
+use crate::stage::{Stage, State};
+use crate::ui::print;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Trigger {
+    /// Reaching a stage
+    Stage(Stage),
+    /// Any condition on the player's state
+    State(fn(&State) -> bool),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Achievement {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub trigger: Trigger,
+}
+
+impl Game {
+    fn is_triggered(&self, trigger: Trigger) -> bool {
+        match trigger {
+            Trigger::Stage(stage) => self.stage == stage || self.state.path.contains(&stage),
+            Trigger::State(condition) => condition(&self.state),
+        }
+    }
+
+    /// Unlocks and announces every achievement whose trigger now holds
+    pub fn check_achievements(&mut self) {
+        for achievement in ACHIEVEMENTS {
+            if self.profile.achievements.contains(achievement.id)
+                || !self.is_triggered(achievement.trigger)
+            {
+                continue;
+            }
+            self.profile.achievements.insert(achievement.id.to_string());
+            msg(&format!(
+                "{{yellow}}*Achievement unlocked:*{{/}} {} - {}\n",
+                achievement.name, achievement.description
+            ));
+            if let Err(e) = self.profile.save() {
+                print(&format!("Could not save your profile: {e}\n"));
+            }
+        }
+    }
+
+    pub fn print_achievements(&self) {
+        let unlocked = ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| self.profile.achievements.contains(achievement.id))
+            .count();
+        print(&format!("*Achievements* ({unlocked}/{})\n", ACHIEVEMENTS.len()));
+        for achievement in ACHIEVEMENTS {
+            if self.profile.achievements.contains(achievement.id) {
+                print(&format!("  *{}* - {}\n", achievement.name, achievement.description));
+            } else {
+                print(&format!("  {} - ???\n", achievement.name));
+            }
+        }
+    }
+}
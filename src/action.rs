@@ -0,0 +1,90 @@
+use crate::lexer::{AdverbKind, VerbKind};
+use crate::rng::Rng;
+use crate::stage::State;
+use crate::status::StatusKind;
+
+/// What performing a single `GameExpr::Svn` costs the player
+#[derive(Debug, Clone, Copy)]
+pub struct ActionCost {
+    pub minutes: u32,
+    /// Percentage of incoming damage taken while performing the action
+    pub damage_percent: u32,
+    /// Rushed actions can fail when the player is in no state to rush
+    pub risky: bool,
+}
+
+impl Default for ActionCost {
+    fn default() -> Self {
+        Self {
+            minutes: 0,
+            damage_percent: 100,
+            risky: false,
+        }
+    }
+}
+
+impl VerbKind {
+    /// Minutes an action with this verb takes at a moderate pace
+    pub fn base_cost(&self) -> u32 {
+        match self {
+            VerbKind::Go | VerbKind::Leave | VerbKind::Walk | VerbKind::Head => 5,
+            VerbKind::Do | VerbKind::Continue => 5,
+            VerbKind::Run | VerbKind::Equip | VerbKind::Roll => 2,
+            VerbKind::Get | VerbKind::Open | VerbKind::Close | VerbKind::Check => 1,
+            VerbKind::Board | VerbKind::Enter | VerbKind::Strike | VerbKind::Remove => 1,
+            VerbKind::Exit => 1,
+            // Waiting lasts as long as whatever is being waited for
+            VerbKind::Wait | VerbKind::Quit | VerbKind::Dummy => 0,
+        }
+    }
+}
+
+impl AdverbKind {
+    pub fn time_percent(&self) -> u32 {
+        match self {
+            AdverbKind::Quick => 50,
+            AdverbKind::Slow => 200,
+            AdverbKind::Careful => 150,
+            AdverbKind::Intense => 75,
+        }
+    }
+
+    pub fn damage_percent(&self) -> u32 {
+        match self {
+            AdverbKind::Quick => 100,
+            AdverbKind::Slow => 100,
+            AdverbKind::Careful => 50,
+            AdverbKind::Intense => 150,
+        }
+    }
+}
+
+impl ActionCost {
+    pub fn of(verb: VerbKind, adverb: Option<AdverbKind>) -> Self {
+        let minutes = verb.base_cost();
+        match adverb {
+            Some(adverb) => Self {
+                minutes: minutes * adverb.time_percent() / 100,
+                damage_percent: adverb.damage_percent(),
+                risky: adverb == AdverbKind::Quick,
+            },
+            None => Self {
+                minutes,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Rushing sometimes fails, and much more often when frozen limbs or
+    /// bleeding wounds slow the player down
+    pub fn fails(&self, state: &State, rng: &mut Rng) -> bool {
+        if !self.risky {
+            return false;
+        }
+        if state.has_status(StatusKind::Frozen) || state.has_status(StatusKind::Bleeding) {
+            rng.chance(60)
+        } else {
+            rng.chance(15)
+        }
+    }
+}
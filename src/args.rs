@@ -0,0 +1,104 @@
+use std::env;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::difficulty::Difficulty;
+
+const USAGE: &str = "\
+Usage: batman [OPTIONS]
+
+Options:
+  --plain              Plain text interface without ncurses
+  --typewriter         Reveal story text one character at a time
+  --text-speed <CPS>   Typewriter speed in characters per second [default: 60]
+  --line-pause <MS>    Typewriter pause after every line [default: 300]
+  --script <FILE>      Record a transcript; .md and .html export Markdown/HTML
+  --difficulty <LEVEL> easy, normal or hard; skips the difficulty prompt
+  --profile <FILE>     Where achievements are kept [default: ~/.batman_profile,
+                       or none while replaying]
+  --seed <SEED>        Seed for random events, to reproduce a run exactly
+  --record <FILE>      Save every line entered so the session can be replayed
+  --replay <FILE>      Feed a recorded session back into the game
+  --step               Wait for a key between replayed lines
+  --replay-delay <MS>  Pause between replayed lines [default: 0]
+  -h, --help           Print this message";
+
+#[derive(Debug, Clone)]
+pub struct Args {
+    pub plain: bool,
+    pub typewriter: bool,
+    pub text_speed: u64,
+    pub line_pause: u64,
+    pub script: Option<PathBuf>,
+    pub difficulty: Option<Difficulty>,
+    pub profile: Option<PathBuf>,
+    pub seed: Option<u64>,
+    pub record: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub step: bool,
+    pub replay_delay: u64,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self {
+            plain: false,
+            typewriter: false,
+            text_speed: 60,
+            line_pause: 300,
+            script: None,
+            difficulty: None,
+            profile: None,
+            seed: None,
+            record: None,
+            replay: None,
+            step: false,
+            replay_delay: 0,
+        }
+    }
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{message}\n\n{USAGE}");
+    std::process::exit(2);
+}
+
+fn value<T: std::str::FromStr>(arg: &str, value: Option<String>) -> T {
+    let Some(value) = value else {
+        usage_error(&format!("Missing value for '{arg}'"));
+    };
+    value
+        .parse()
+        .unwrap_or_else(|_| usage_error(&format!("Invalid value '{value}' for '{arg}'")))
+}
+
+impl Args {
+    /// Parses the command line, exiting with usage on anything unexpected
+    pub fn parse() -> Self {
+        let mut args = Self::default();
+        let mut argv = env::args().skip(1);
+        while let Some(arg) = argv.next() {
+            match arg.as_str() {
+                "--plain" => args.plain = true,
+                "--typewriter" => args.typewriter = true,
+                "--text-speed" => args.text_speed = value(&arg, argv.next()),
+                "--line-pause" => args.line_pause = value(&arg, argv.next()),
+                "--script" => args.script = Some(value(&arg, argv.next())),
+                "--difficulty" => args.difficulty = Some(value(&arg, argv.next())),
+                "--profile" => args.profile = Some(value(&arg, argv.next())),
+                "--seed" => args.seed = Some(value(&arg, argv.next())),
+                "--record" => args.record = Some(value(&arg, argv.next())),
+                "--replay" => args.replay = Some(value(&arg, argv.next())),
+                "--step" => args.step = true,
+                "--replay-delay" => args.replay_delay = value(&arg, argv.next()),
+                "-h" | "--help" => {
+                    // Printing may fail when piped into e.g. `head`
+                    let _ = writeln!(io::stdout(), "{USAGE}");
+                    std::process::exit(0);
+                }
+                _ => usage_error(&format!("Unknown option '{arg}'")),
+            }
+        }
+        args
+    }
+}
@@ -0,0 +1,153 @@
+use crate::choice::Choice;
+use crate::game::{Equipment, Game, GameErr, Result};
+
+/// Longest name the status line and sidebar have room for
+pub const MAX_NAME_LEN: usize = 20;
+
+/// Checks a name typed at the start of the game and tidies its whitespace
+pub fn validate_name(name: &str) -> Result<String> {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        return Err(GameErr::InvalidName("Every hero needs a name; type yours"));
+    }
+    if name.chars().any(char::is_control) {
+        return Err(GameErr::InvalidName("Names can't contain control characters"));
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(GameErr::InvalidName("That name is too long; try something shorter"));
+    }
+    Ok(name)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Pronouns {
+    He,
+    She,
+    #[default]
+    They,
+}
+
+impl Pronouns {
+    pub const CHOICES: [Choice; 3] = [
+        Choice::new("he", "he/him", &["he", "him"]),
+        Choice::new("she", "she/her", &["she", "her"]),
+        Choice::new("they", "they/them", &["they", "them"]),
+    ];
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "he" => Some(Pronouns::He),
+            "she" => Some(Pronouns::She),
+            "they" => Some(Pronouns::They),
+            _ => None,
+        }
+    }
+
+    pub fn subject(&self) -> &'static str {
+        match self {
+            Pronouns::He => "he",
+            Pronouns::She => "she",
+            Pronouns::They => "they",
+        }
+    }
+
+    pub fn object(&self) -> &'static str {
+        match self {
+            Pronouns::He => "him",
+            Pronouns::She => "her",
+            Pronouns::They => "them",
+        }
+    }
+
+    pub fn possessive(&self) -> &'static str {
+        match self {
+            Pronouns::He => "his",
+            Pronouns::She => "her",
+            Pronouns::They => "their",
+        }
+    }
+}
+
+/// What the player did before the game, for a head start
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Background {
+    #[default]
+    Student,
+    Athlete,
+    Yooper,
+    Fencer,
+}
+
+impl Background {
+    pub const CHOICES: [Choice; 4] = [
+        Choice::new("student", "Student - no head start, just grit", &[]),
+        Choice::new("athlete", "Athlete - extra health", &[]),
+        Choice::new("yooper", "Yooper - already wearing a winter coat", &["coat"]),
+        Choice::new("fencer", "Fencer - never without a sword", &["sword"]),
+    ];
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "student" => Some(Background::Student),
+            "athlete" => Some(Background::Athlete),
+            "yooper" => Some(Background::Yooper),
+            "fencer" => Some(Background::Fencer),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Background::Student => "Student",
+            Background::Athlete => "Athlete",
+            Background::Yooper => "Yooper",
+            Background::Fencer => "Fencer",
+        }
+    }
+}
+
+impl Game {
+    /// Gives the player their background's attributes and items
+    pub fn set_background(&mut self, background: Background) {
+        self.state.background = background;
+        match background {
+            Background::Student => (),
+            Background::Athlete => self.state.health += 4,
+            Background::Yooper => {
+                self.state.equipment.insert(Equipment::Coat);
+            }
+            Background::Fencer => {
+                self.state.equipment.insert(Equipment::Sword);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_tidied() {
+        assert_eq!(validate_name("  Bob   the\tBrave \n").unwrap(), "Bob the Brave");
+    }
+
+    #[test]
+    fn empty_names_are_rejected() {
+        assert!(validate_name("").is_err());
+        assert!(validate_name(" \t\r\n ").is_err());
+    }
+
+    #[test]
+    fn control_characters_are_rejected() {
+        assert!(validate_name("Bob\u{7}").is_err());
+        assert!(validate_name("\u{1b}[31mBob").is_err());
+    }
+
+    #[test]
+    fn long_names_are_rejected() {
+        let longest = "é".repeat(MAX_NAME_LEN);
+        assert_eq!(validate_name(&longest).unwrap(), longest);
+        assert!(validate_name(&"a".repeat(MAX_NAME_LEN + 1)).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use crate::choice::Choice;
+use crate::game::{msg, Equipment, Game, ParseMode};
+use crate::save::Snapshot;
+use crate::stage::{Stage, State};
+use crate::ui::print;
+
+impl Stage {
+    /// Stages the game remembers so a lost run can be retried from there
+    pub fn is_checkpoint(&self) -> bool {
+        matches!(self, Stage::BusArrive | Stage::CampusDragon)
+    }
+}
+
+impl Game {
+    /// Whether the run can still be won from `stage`; without the sword the
+    /// dragon can't be beaten, and without warmth the walk is fatal
+    fn is_winnable(&self, stage: Stage) -> bool {
+        let has_sword = self.state.equipment.contains(&Equipment::Sword);
+        match stage {
+            Stage::BusArrive => has_sword && !self.walk_kills(),
+            Stage::CampusDragon => has_sword,
+            _ => true,
+        }
+    }
+
+    /// Remembers the run as it is on first entering a checkpoint stage, which
+    /// is read with `parse_mode`; a run that is already lost is not worth
+    /// retrying
+    pub fn reach_checkpoint(&mut self, stage: Stage, parse_mode: ParseMode) {
+        if self.checkpoint.as_ref().is_some_and(|checkpoint| checkpoint.stage == stage) {
+            return;
+        }
+        if !self.is_winnable(stage) {
+            return;
+        }
+        self.checkpoint = Some(Snapshot {
+            stage,
+            parse_mode,
+            state: self.state.clone(),
+            rng: self.rng.clone(),
+        });
+        print("{green}Checkpoint reached{/}\n");
+    }
+
+    /// Goes back to the last checkpoint; `None` if there is none yet
+    pub fn retry(&mut self) -> Option<Stage> {
+        let checkpoint = self.checkpoint.clone()?;
+        self.rewind_state(checkpoint.state);
+        self.rng = checkpoint.rng;
+        self.snapshots.clear();
+        msg("You get another chance...\n");
+        Some(checkpoint.stage)
+    }
+
+    /// Starts a new run with the same character and difficulty, or from the
+    /// very beginning if no run has started yet
+    pub fn restart(&mut self) {
+        if self.state.path.is_empty() {
+            self.transition(Stage::First);
+        } else {
+            self.reset_run();
+            self.transition(Stage::Library);
+        }
+    }
+
+    /// Sets up a new run for the same character, ready for the library
+    pub fn reset_run(&mut self) {
+        let difficulty = self.state.difficulty;
+        let background = self.state.background;
+        self.state = State {
+            name: self.state.name.clone(),
+            pronouns: self.state.pronouns,
+            ..State::default()
+        };
+        self.set_background(background);
+        self.set_difficulty(difficulty);
+        self.delay_buses();
+        self.snapshots.clear();
+        self.checkpoint = None;
+        print("Starting over...\n");
+    }
+
+    /// What a lost run can do next
+    pub fn retry_choices(&self) -> Vec<Choice> {
+        let mut choices = Vec::new();
+        if self.checkpoint.is_some() {
+            choices.push(Choice::new("retry", "Retry from the last checkpoint", &["retry", "checkpoint"]));
+        }
+        choices.push(Choice::new("restart", "Restart", &["start over", "yes"]));
+        choices.push(Choice::new("quit", "Quit", &["exit", "no"]));
+        choices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(game: &mut Game, lines: &[&str]) {
+        game.transition(Stage::First);
+        for line in lines {
+            game.eval(line);
+        }
+    }
+
+    #[test]
+    fn checkpoints_a_winnable_run() {
+        let mut game = Game::with_seed(7);
+        play(&mut game, &["Bob", "1", "1", "yes", "2", "equip coat", "equip sword", "leave the building", "wait for the bus"]);
+        assert_eq!(game.stage, Stage::BusArrive);
+        assert!(game.checkpoint.is_some());
+    }
+
+    #[test]
+    fn skips_a_run_that_is_already_lost() {
+        let mut game = Game::with_seed(7);
+        play(&mut game, &["Bob", "1", "1", "yes", "2", "leave the building", "wait for the bus"]);
+        assert_eq!(game.stage, Stage::BusArrive);
+        assert!(game.checkpoint.is_none());
+        assert!(game.retry_choices().iter().all(|choice| choice.id != "retry"));
+    }
+}
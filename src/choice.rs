@@ -0,0 +1,87 @@
+use crate::game::{Game, ParseMode};
+use crate::ui::print;
+
+/// One numbered option of a choice prompt
+#[derive(Debug, Clone, Copy)]
+pub struct Choice {
+    /// Handed to the stage as `GameExpr::Choice` when picked
+    pub id: &'static str,
+    pub label: &'static str,
+    /// Words that pick this option besides its number
+    pub keywords: &'static [&'static str],
+}
+
+impl Choice {
+    pub const fn new(id: &'static str, label: &'static str, keywords: &'static [&'static str]) -> Self {
+        Self { id, label, keywords }
+    }
+
+    fn is_named(&self, input: &str) -> bool {
+        input == self.id
+            || input == self.label.to_ascii_lowercase()
+            || self.keywords.contains(&input)
+    }
+}
+
+impl Game {
+    /// Lists `choices` and switches to reading the player's pick
+    pub fn offer(&mut self, choices: Vec<Choice>) -> ParseMode {
+        for (i, choice) in choices.iter().enumerate() {
+            print(&format!("  {}. {}\n", i + 1, choice.label));
+        }
+        self.choices = choices;
+        ParseMode::Choice
+    }
+
+    /// The id of the choice `input` picks, by number or by name
+    pub fn choose(&self, input: &str) -> Option<&'static str> {
+        let input = input.trim().to_ascii_lowercase();
+        let choice = match input.parse::<usize>() {
+            Ok(n) => self.choices.get(n.checked_sub(1)?),
+            Err(_) => self.choices.iter().find(|choice| choice.is_named(&input)),
+        };
+        choice.map(|choice| choice.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game() -> Game {
+        let mut game = Game::with_seed(1);
+        game.offer(vec![
+            Choice::new("retry", "Retry from the last checkpoint", &["checkpoint"]),
+            Choice::new("quit", "Quit", &["exit", "no"]),
+        ]);
+        game
+    }
+
+    #[test]
+    fn picks_by_number() {
+        let game = game();
+        assert_eq!(game.choose("1"), Some("retry"));
+        assert_eq!(game.choose("2"), Some("quit"));
+        assert_eq!(game.choose("0"), None);
+        assert_eq!(game.choose("3"), None);
+        assert_eq!(game.choose("-1"), None);
+    }
+
+    #[test]
+    fn picks_by_id_label_or_keyword() {
+        let game = game();
+        assert_eq!(game.choose("retry"), Some("retry"));
+        assert_eq!(game.choose("Retry from the last checkpoint"), Some("retry"));
+        assert_eq!(game.choose("checkpoint"), Some("retry"));
+        assert_eq!(game.choose("EXIT"), Some("quit"));
+        assert_eq!(game.choose("maybe"), None);
+        assert_eq!(game.choose(""), None);
+    }
+
+    #[test]
+    fn ignores_surrounding_whitespace() {
+        let game = game();
+        assert_eq!(game.choose("  2\n"), Some("quit"));
+        assert_eq!(game.choose("\tno "), Some("quit"));
+    }
+}
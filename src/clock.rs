@@ -0,0 +1,209 @@
+use std::fmt;
+
+use crate::ui::print;
+
+use crate::game::{msg, Game};
+use crate::stage::State;
+
+pub const DATE: &str = "Tuesday, January 16th";
+
+/// Time of day, in minutes since midnight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Clock {
+    minutes: u32,
+}
+
+pub const START_TIME: Clock = Clock::at(8, 5);
+pub const CLASS_TIME: Clock = Clock::at(9, 5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    BusArrives,
+    BusDeparts,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Scheduled {
+    pub at: Clock,
+    pub event: Event,
+}
+
+/// The campus shuttle stops outside the library every twenty minutes and
+/// waits two minutes before pulling away
+pub fn bus_timetable() -> Vec<Scheduled> {
+    [Clock::at(8, 20), Clock::at(8, 40), Clock::at(9, 0)]
+        .into_iter()
+        .flat_map(|at| {
+            [
+                Scheduled { at, event: Event::BusArrives },
+                Scheduled { at: at.after(2), event: Event::BusDeparts },
+            ]
+        })
+        .collect()
+}
+
+impl Clock {
+    pub const fn at(hour: u32, minute: u32) -> Self {
+        Self { minutes: hour * 60 + minute }
+    }
+
+    pub fn hour(&self) -> u32 {
+        self.minutes / 60 % 24
+    }
+
+    pub fn minute(&self) -> u32 {
+        self.minutes % 60
+    }
+
+    pub fn after(&self, minutes: u32) -> Self {
+        Self { minutes: self.minutes + minutes }
+    }
+
+    /// Signed number of minutes from `self` until `other`
+    pub fn minutes_until(&self, other: Clock) -> i32 {
+        other.minutes as i32 - self.minutes as i32
+    }
+}
+
+impl fmt::Display for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (hour, suffix) = match self.hour() {
+            0 => (12, "AM"),
+            h @ 1..=11 => (h, "AM"),
+            12 => (12, "PM"),
+            h => (h - 12, "PM"),
+        };
+        write!(f, "{hour:02}:{:02} {suffix}", self.minute())
+    }
+}
+
+impl State {
+    pub fn time_left(&self) -> i32 {
+        self.clock.minutes_until(self.class_time)
+    }
+
+    fn next_event(&self, event: Event) -> Option<Clock> {
+        self.schedule
+            .iter()
+            .filter(|scheduled| scheduled.event == event)
+            .map(|scheduled| scheduled.at)
+            .min()
+    }
+}
+
+impl Game {
+    /// Makes every bus on the timetable run up to five minutes late
+    pub fn delay_buses(&mut self) {
+        let mut lateness = 0;
+        for scheduled in &mut self.state.schedule {
+            // A bus leaves as late as it arrived
+            if scheduled.event == Event::BusArrives {
+                lateness = self.rng.range(0, 5);
+            }
+            scheduled.at = scheduled.at.after(lateness);
+        }
+    }
+
+    pub fn print_clock(&self) {
+        print(&format!("It is {} on {DATE}\n", self.state.clock));
+    }
+
+    /// Moves the clock forward, firing every scheduled event along the way
+    pub fn pass_time(&mut self, minutes: u32) {
+        let before = self.state.time_left();
+        let target = self.state.clock.after(minutes);
+
+        while let Some(i) = self
+            .state
+            .schedule
+            .iter()
+            .enumerate()
+            .filter(|(_, scheduled)| scheduled.at <= target)
+            .min_by_key(|(_, scheduled)| scheduled.at)
+            .map(|(i, _)| i)
+        {
+            let scheduled = self.state.schedule.remove(i);
+            self.state.clock = scheduled.at;
+            self.fire(scheduled.event);
+        }
+        self.state.clock = target;
+
+        let after = self.state.time_left();
+        if let Some(warning) = self
+            .time_warnings
+            .iter()
+            .filter(|&&warning| before > warning && after <= warning && after > 0)
+            .min()
+        {
+            msg(&format!("{{yellow}}*Hurry!*{{/}} Class starts in {warning} minutes or less...\n"));
+        }
+    }
+
+    /// Passes time until the next time `event` happens; returns false if it
+    /// never will
+    pub fn wait_for(&mut self, event: Event) -> bool {
+        if event == Event::BusArrives && self.state.bus_at_stop {
+            return true;
+        }
+        match self.state.next_event(event) {
+            Some(at) => {
+                let minutes = self.state.clock.minutes_until(at).max(0) as u32;
+                self.pass_time(minutes);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn fire(&mut self, event: Event) {
+        match event {
+            Event::BusArrives => {
+                self.state.bus_at_stop = true;
+                self.state.bus_arrived_at = Some(self.state.clock);
+            }
+            Event::BusDeparts => self.state.bus_at_stop = false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_uses_twelve_hour_time() {
+        assert_eq!(Clock::at(0, 5).to_string(), "12:05 AM");
+        assert_eq!(Clock::at(9, 5).to_string(), "09:05 AM");
+        assert_eq!(Clock::at(12, 0).to_string(), "12:00 PM");
+        assert_eq!(Clock::at(13, 30).to_string(), "01:30 PM");
+        assert_eq!(Clock::at(23, 59).after(1).to_string(), "12:00 AM");
+    }
+
+    #[test]
+    fn pass_time_fires_events_in_order() {
+        let mut game = Game::with_seed(1);
+        game.pass_time(15);
+        assert_eq!(game.state.clock, Clock::at(8, 20));
+        assert!(game.state.bus_at_stop);
+        assert_eq!(game.state.bus_arrived_at, Some(Clock::at(8, 20)));
+
+        // Skipping past a whole stop leaves the bus gone
+        game.pass_time(25);
+        assert_eq!(game.state.clock, Clock::at(8, 45));
+        assert!(!game.state.bus_at_stop);
+        assert_eq!(game.state.bus_arrived_at, Some(Clock::at(8, 40)));
+        assert_eq!(game.state.schedule.len(), 2);
+    }
+
+    #[test]
+    fn wait_for_stops_at_the_event() {
+        let mut game = Game::with_seed(1);
+        assert!(game.wait_for(Event::BusArrives));
+        assert_eq!(game.state.clock, Clock::at(8, 20));
+        assert!(game.wait_for(Event::BusDeparts));
+        assert_eq!(game.state.clock, Clock::at(8, 22));
+
+        game.pass_time(60);
+        assert!(!game.wait_for(Event::BusArrives));
+    }
+}
@@ -0,0 +1,471 @@
+use std::iter;
+
+use crate::action::ActionCost;
+use crate::game::{msg, Game, ParseMode, HELP};
+use crate::lexer::{self, Verb, VerbKind, ADJECTIVES, ADVERBS, ARTICLES, NOUNS, VERBS};
+use crate::markup;
+use crate::stage::Stage;
+use crate::ui::{self, print};
+
+const ANY_MODE: &[ParseMode] = &[
+    ParseMode::Grammar,
+    ParseMode::Raw,
+    ParseMode::Confirm,
+    ParseMode::Choice,
+];
+const IN_RUN: &[ParseMode] = &[ParseMode::Grammar, ParseMode::Confirm, ParseMode::Choice];
+
+/// A command about the game rather than an action in it
+pub struct MetaCommand {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    /// How the command is written in help, e.g. `save [file]`
+    pub usage: &'static str,
+    pub summary: &'static str,
+    pub details: &'static str,
+    pub takes_args: bool,
+    /// Parse modes in which the command is understood
+    pub modes: &'static [ParseMode],
+    /// Whether the command is replayed when a saved run is loaded
+    pub saved: bool,
+    pub run: fn(&mut Game, &str),
+}
+
+pub const COMMANDS: &[MetaCommand] = &[
+    MetaCommand {
+        name: "help",
+        aliases: &["?"],
+        usage: "help [topic]",
+        summary: "Prints this, or explains a command or verb",
+        details: "Lists every command; 'help <command>' or 'help <verb>' explains just that one, \
+'help grammar' explains how actions are written and 'help commands' lists only the commands.",
+        takes_args: true,
+        modes: ANY_MODE,
+        saved: false,
+        run: |game, args| game.eval_help(args),
+    },
+    MetaCommand {
+        name: "hint",
+        aliases: &[],
+        usage: "hint",
+        summary: "Prints stage related hints",
+        details: "Hints are limited, and cost points, on normal and hard difficulty.",
+        takes_args: false,
+        modes: ANY_MODE,
+        saved: true,
+        run: |game, _| {
+            if game.take_hint() {
+                game.print_hint();
+            }
+        },
+    },
+    MetaCommand {
+        name: "quit",
+        aliases: &["exit", "exit game", "quit game", "close game"],
+        usage: "quit",
+        summary: "Exits the game",
+        details: "Exits right away; save first if you want to come back to this run.",
+        takes_args: false,
+        modes: ANY_MODE,
+        saved: false,
+        run: |game, _| game.transition(Stage::Quit),
+    },
+    MetaCommand {
+        name: "restart",
+        aliases: &["start over", "new game"],
+        usage: "restart",
+        summary: "Starts over from the beginning",
+        details: "Abandons the current run and starts a new one with the same character and difficulty.",
+        takes_args: false,
+        modes: ANY_MODE,
+        saved: true,
+        run: |game, _| game.restart(),
+    },
+    MetaCommand {
+        name: "save",
+        aliases: &[],
+        usage: "save [file]",
+        summary: "Saves the current run",
+        details: "Saves to batman.sav unless a file is given.",
+        takes_args: true,
+        modes: IN_RUN,
+        saved: false,
+        run: |game, args| game.save(args),
+    },
+    MetaCommand {
+        name: "load",
+        aliases: &["restore"],
+        usage: "load [file]",
+        summary: "Loads a saved run",
+        details: "Loads batman.sav unless a file is given.",
+        takes_args: true,
+        modes: ANY_MODE,
+        saved: false,
+        run: |game, args| game.load(args),
+    },
+    MetaCommand {
+        name: "undo",
+        aliases: &["rewind", "rewind time"],
+        usage: "undo",
+        summary: "Goes back one step",
+        details: "Takes back your last action, even a fatal one; use it again to go further back.",
+        takes_args: false,
+        modes: IN_RUN,
+        saved: true,
+        run: |game, _| game.undo(),
+    },
+    MetaCommand {
+        name: "score",
+        aliases: &["points"],
+        usage: "score",
+        summary: "Shows your score so far",
+        details: "Lists the points earned for each discovery this run.",
+        takes_args: false,
+        modes: IN_RUN,
+        saved: false,
+        run: |game, _| game.print_score(),
+    },
+    MetaCommand {
+        name: "stats",
+        aliases: &["check stats"],
+        usage: "stats",
+        summary: "Shows your health, armor and equipment",
+        details: "Also shows the seed, to share or replay this run.",
+        takes_args: false,
+        modes: IN_RUN,
+        saved: false,
+        run: |game, _| game.print_stats(),
+    },
+    MetaCommand {
+        name: "time",
+        aliases: &["what time is it", "what time is it?"],
+        usage: "time",
+        summary: "Shows how long until class",
+        details: "Checking the time does not take any.",
+        takes_args: false,
+        modes: IN_RUN,
+        saved: false,
+        run: |game, _| game.print_time_left(),
+    },
+    MetaCommand {
+        name: "verbose",
+        aliases: &[],
+        usage: "verbose",
+        summary: "Describes the scene again after every action",
+        details: "This is the default; see also 'brief'.",
+        takes_args: false,
+        modes: ANY_MODE,
+        saved: false,
+        run: |game, _| {
+            game.verbose = true;
+            print("Scenes will be described in full\n");
+        },
+    },
+    MetaCommand {
+        name: "brief",
+        aliases: &[],
+        usage: "brief",
+        summary: "Only names the scene after actions that stay in it",
+        details: "New scenes are still described in full; see also 'verbose'.",
+        takes_args: false,
+        modes: ANY_MODE,
+        saved: false,
+        run: |game, _| {
+            game.verbose = false;
+            print("Scenes you stay in will only be named\n");
+        },
+    },
+    MetaCommand {
+        name: "script",
+        aliases: &[],
+        usage: "script [markdown|html]",
+        summary: "Records a transcript of the session",
+        details: "Writes to batman-<time>.txt, .md or .html in the current directory.",
+        takes_args: true,
+        modes: ANY_MODE,
+        saved: false,
+        run: |game, args| game.eval_script(args),
+    },
+    MetaCommand {
+        name: "unscript",
+        aliases: &[],
+        usage: "unscript",
+        summary: "Stops recording the transcript",
+        details: "The transcript so far is kept.",
+        takes_args: false,
+        modes: ANY_MODE,
+        saved: false,
+        run: |game, _| game.eval_unscript(),
+    },
+    MetaCommand {
+        name: "achievements",
+        aliases: &[],
+        usage: "achievements",
+        summary: "Lists the achievements you have unlocked",
+        details: "Achievements are kept in your profile between runs.",
+        takes_args: false,
+        modes: ANY_MODE,
+        saved: false,
+        run: |game, _| game.print_achievements(),
+    },
+    MetaCommand {
+        name: "endings",
+        aliases: &[],
+        usage: "endings",
+        summary: "Lists the endings you have found",
+        details: "Endings are kept in your profile between runs.",
+        takes_args: false,
+        modes: ANY_MODE,
+        saved: false,
+        run: |game, _| game.print_endings(),
+    },
+];
+
+impl MetaCommand {
+    fn names(&self) -> impl Iterator<Item = &'static str> {
+        iter::once(self.name).chain(self.aliases.iter().copied())
+    }
+
+    /// The arguments of `command`, if it invokes this command; names match
+    /// in any case but the arguments keep theirs
+    fn arguments<'a>(&self, command: &'a str) -> Option<&'a str> {
+        self.names().find_map(|name| {
+            let prefix = command.get(..name.len())?;
+            if !prefix.eq_ignore_ascii_case(name) {
+                return None;
+            }
+            let rest = &command[name.len()..];
+            if rest.is_empty() {
+                Some(rest)
+            } else if self.takes_args && rest.starts_with(' ') {
+                Some(rest.trim())
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn is_available(&self, mode: ParseMode) -> bool {
+        self.modes.contains(&mode)
+    }
+}
+
+/// The meta command `command` invokes and its arguments
+pub fn lookup(command: &str) -> Option<(&'static MetaCommand, &str)> {
+    COMMANDS
+        .iter()
+        .find_map(|meta| meta.arguments(command).map(|args| (meta, args)))
+}
+
+/// The meta command called `name` or one of its aliases
+pub fn find(name: &str) -> Option<&'static MetaCommand> {
+    COMMANDS.iter().find(|meta| meta.names().any(|n| n == name))
+}
+
+impl Game {
+    /// Runs a meta command; returns false if it should be read as ordinary
+    /// input instead
+    pub fn eval_meta(&mut self, meta: &MetaCommand, args: &str) -> bool {
+        if !meta.is_available(self.parse_mode) {
+            // Raw input such as a name is free to look like a command
+            if matches!(self.parse_mode, ParseMode::Raw) {
+                return false;
+            }
+            print(&format!("'{}' isn't available right now\n", meta.name));
+            return true;
+        }
+        (meta.run)(self, args);
+        true
+    }
+
+    pub fn eval_help(&mut self, args: &str) {
+        let args = args.to_ascii_lowercase();
+        let args = args.as_str();
+        match args {
+            "" => self.print_help(),
+            "commands" => msg(&command_list()),
+            "grammar" => msg(&grammar()),
+            _ => {
+                // Some words, like 'quit', are both a command and a verb
+                let help: Vec<String> = find(args)
+                    .map(|meta| self.command_help(meta))
+                    .into_iter()
+                    .chain(lexer::find_verb(args).map(verb_help))
+                    .collect();
+                if help.is_empty() {
+                    print(&format!("There is no command or verb '{args}'; try 'help'\n"));
+                } else {
+                    msg(&help.join("\n"));
+                }
+            }
+        }
+    }
+
+    fn command_help(&self, meta: &MetaCommand) -> String {
+        let mut s = format!("*{}* - {}\n", markup::escape(meta.usage), meta.summary);
+        s.push_str(meta.details);
+        s.push('\n');
+        if !meta.aliases.is_empty() {
+            s.push_str(&format!("Also: {}\n", meta.aliases.join(", ")));
+        }
+        if !meta.is_available(self.parse_mode) {
+            s.push_str("Not available right now\n");
+        }
+        s
+    }
+
+    pub fn print_help(&mut self) {
+        ui::separator();
+        let mut s = command_list();
+        s.push('\n');
+        s.push_str(HELP);
+        s.push_str("\nType 'help <verb>', 'help grammar' or 'help commands' for more\n");
+        msg(&s);
+    }
+}
+
+fn command_list() -> String {
+    let mut s = String::from("*Commands*\n");
+    for meta in COMMANDS {
+        let usage = format!("{:<24}", meta.usage);
+        s.push_str(&format!("{} {}\n", markup::escape(&usage), meta.summary));
+    }
+    s
+}
+
+/// Every entry of `table`, with its synonyms, e.g. `time/clock`
+fn all_words<K>(table: &[(K, &[&str])]) -> String {
+    let words: Vec<_> = table.iter().map(|(_, words)| words.join("/")).collect();
+    words.join(", ")
+}
+
+fn minutes(n: u32) -> String {
+    format!("{n} minute{}", if n == 1 { "" } else { "s" })
+}
+
+fn verb_help(verb: &Verb) -> String {
+    let mut s = format!("*{}* - {}\n", verb.words[0], verb.summary);
+    if verb.words.len() > 1 {
+        s.push_str(&format!("Also: {}\n", verb.words[1..].join(", ")));
+    }
+    let objects: Vec<_> = verb.objects().into_iter().map(lexer::noun_name).collect();
+    if objects.is_empty() {
+        s.push_str("Takes an object, but nothing this morning responds to it\n");
+    } else {
+        s.push_str(&format!("Objects: {}\n", objects.join(", ")));
+    }
+    // Quitting never reaches the clock, so it has no cost to describe
+    if verb.kind == VerbKind::Quit {
+        s.push_str("Takes no time and no adverbs; it ends the game\n");
+    } else {
+        let base = ActionCost::of(verb.kind, None);
+        match (verb.kind, base.minutes) {
+            (_, 0) => s.push_str("Takes as long as whatever you wait for\n"),
+            // Checking your stats or the time is answered before any cost
+            (VerbKind::Check, n) => {
+                s.push_str(&format!("Takes {}, but checking your stats or the time is free\n", minutes(n)))
+            }
+            (_, n) => s.push_str(&format!("Takes {}\n", minutes(n))),
+        }
+        s.push_str("Adverbs:\n");
+        for &(adverb, words) in ADVERBS {
+            let cost = ActionCost::of(verb.kind, Some(adverb));
+            let mut effects = Vec::new();
+            if base.minutes > 0 {
+                effects.push(minutes(cost.minutes));
+            }
+            effects.push(format!("{}% damage", cost.damage_percent));
+            if cost.risky {
+                effects.push("may fail".to_string());
+            }
+            s.push_str(&format!("  {:<10} {}\n", words.join("/"), effects.join(", ")));
+        }
+    }
+    if !verb.examples.is_empty() {
+        s.push_str("Examples:\n");
+        for example in verb.examples {
+            s.push_str(&format!("  {example}\n"));
+        }
+    }
+    s
+}
+
+fn grammar() -> String {
+    let mut s = String::from(HELP.trim_end());
+    s.push_str("\n\nFiller words are skipped: ");
+    s.push_str(&ARTICLES.join(", "));
+    s.push_str("\n\n*Adverbs* change how long an action takes and how much it hurts\n");
+    for &(adverb, words) in ADVERBS {
+        s.push_str(&format!(
+            "  {:<10} {}% time, {}% damage\n",
+            words.join("/"),
+            adverb.time_percent(),
+            adverb.damage_percent()
+        ));
+    }
+    s.push_str("\n*Adjectives* go before the object, e.g. 'leave the far building'\n");
+    s.push_str(&format!("  {}\n", all_words(ADJECTIVES)));
+    let verbs: Vec<_> = VERBS.iter().map(|verb| verb.words.join("/")).collect();
+    s.push_str(&format!("\n*Verbs*\n  {}\n", verbs.join(", ")));
+    s.push_str(&format!("\n*Nouns*\n  {}\n", all_words(NOUNS)));
+    s.push_str("\nType 'help <verb>' to see how a verb is used\n");
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn name_and_args(command: &str) -> Option<(&'static str, &str)> {
+        lookup(command).map(|(meta, args)| (meta.name, args))
+    }
+
+    #[test]
+    fn finds_commands_and_aliases() {
+        assert_eq!(name_and_args("undo"), Some(("undo", "")));
+        assert_eq!(name_and_args("rewind time"), Some(("undo", "")));
+        assert_eq!(name_and_args("?"), Some(("help", "")));
+        assert_eq!(name_and_args("what time is it?"), Some(("time", "")));
+        assert_eq!(name_and_args("walk to campus"), None);
+    }
+
+    #[test]
+    fn names_match_in_any_case() {
+        assert_eq!(name_and_args("UNDO"), Some(("undo", "")));
+        assert_eq!(name_and_args("Exit Game"), Some(("quit", "")));
+    }
+
+    #[test]
+    fn arguments_keep_their_case() {
+        assert_eq!(name_and_args("SAVE /tmp/MySave.sav"), Some(("save", "/tmp/MySave.sav")));
+        assert_eq!(name_and_args("help   Walk "), Some(("help", "Walk")));
+    }
+
+    #[test]
+    fn only_some_commands_take_arguments() {
+        assert_eq!(name_and_args("undo twice"), None);
+        // A command name must be a whole word
+        assert_eq!(name_and_args("saved"), None);
+        assert_eq!(name_and_args("timeline"), None);
+    }
+
+    #[test]
+    fn verb_examples_parse() {
+        for verb in VERBS {
+            for example in verb.examples {
+                let expr = Parser::new(example).parse_next();
+                assert!(matches!(expr, Ok(Some(_))), "'{example}' does not parse: {expr:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn verb_help_follows_action_costs() {
+        let help = |word| verb_help(lexer::find_verb(word).unwrap());
+        assert!(help("close").contains("Takes 1 minute\n"));
+        assert!(help("leave").contains("Objects: building, back\n"));
+        assert!(help("leave").contains("quick      2 minutes, 100% damage, may fail\n"));
+        assert!(help("wait").contains("quick      100% damage, may fail\n"));
+    }
+}
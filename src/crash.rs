@@ -0,0 +1,61 @@
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::env;
+use std::fs;
+use std::panic::{self, PanicHookInfo};
+
+use crate::transcript::{timestamp, unix_time};
+use crate::ui;
+
+/// Input lines kept for the crash report
+const RECENT_INPUT: usize = 20;
+
+thread_local! {
+    static INPUT: RefCell<VecDeque<String>> = const { RefCell::new(VecDeque::new()) };
+}
+
+/// Remembers a line the player entered, to be included in a crash report
+pub fn note_input(line: &str) {
+    INPUT.with(|input| {
+        let mut input = input.borrow_mut();
+        if input.len() == RECENT_INPUT {
+            input.pop_front();
+        }
+        input.push_back(line.to_string());
+    });
+}
+
+/// Restores the terminal and writes a crash report to the temp directory
+/// before the default panic message is printed
+pub fn install_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        ui::restore_terminal();
+        let now = unix_time();
+        let path = env::temp_dir().join(format!("batman-crash-{now}.txt"));
+        match fs::write(&path, report(info, now)) {
+            Ok(()) => eprintln!("The game crashed; a report was written to '{}'", path.display()),
+            Err(e) => eprintln!("The game crashed and the report could not be written: {e}"),
+        }
+        default_hook(info);
+    }));
+}
+
+fn report(info: &PanicHookInfo, now: u64) -> String {
+    let mut report = format!(
+        "batman {} crash report, {}\n\n{info}\n\n",
+        env!("CARGO_PKG_VERSION"),
+        timestamp(now)
+    );
+    report.push_str("Recent input:\n");
+    let _ = INPUT.try_with(|input| {
+        if let Ok(input) = input.try_borrow() {
+            for line in input.iter() {
+                report.push_str(&format!("> {line}\n"));
+            }
+        }
+    });
+    report.push_str(&format!("\nBacktrace:\n{}\n", Backtrace::force_capture()));
+    report
+}
@@ -0,0 +1,110 @@
+use crate::game::{Equipment, Game};
+use crate::ending::Ending;
+use crate::stage::State;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DamageKind {
+    Physical,
+    Fire,
+    Cold,
+}
+
+impl Equipment {
+    /// Percentage of `kind` damage absorbed while this item is equipt
+    pub fn resistance(&self, kind: DamageKind) -> u32 {
+        match (self, kind) {
+            (Equipment::Coat, DamageKind::Physical) => 4,
+            (Equipment::Coat, DamageKind::Cold) => 80,
+            (Equipment::Coat, DamageKind::Fire) => 0,
+            (Equipment::Sword, _) => 0,
+        }
+    }
+}
+
+impl State {
+    /// Combined resistance of all equipment minus status penalties, capped
+    /// at 100%
+    pub fn resistance(&self, kind: DamageKind) -> u32 {
+        let resistance: u32 = self.equipment.iter().map(|item| item.resistance(kind)).sum();
+        let penalty: u32 = self.status.iter().map(|effect| effect.kind.resistance_penalty(kind)).sum();
+        resistance.saturating_sub(penalty).min(100)
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health <= 0
+    }
+}
+
+impl Game {
+    /// How much of `n` points of `kind` damage would go through, scaled by
+    /// the difficulty and the current action and reduced by resistances
+    pub fn damage_for(&self, kind: DamageKind, n: u32) -> u32 {
+        let n = n * self.state.difficulty.damage_percent() / 100;
+        let n = n * self.action.damage_percent / 100;
+        n * (100 - self.state.resistance(kind)) / 100
+    }
+
+    /// Applies `n` points of `kind` damage and returns how much actually
+    /// went through
+    pub fn do_damage(&mut self, kind: DamageKind, n: u32) -> u32 {
+        let damage = self.damage_for(kind, n);
+        self.state.health -= damage as i32;
+        self.state.damage_taken += damage;
+        if self.state.is_dead() && self.state.ending.is_none() {
+            self.state.ending = Some(Ending::of_death(kind, &self.state));
+        }
+        damage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::ActionCost;
+    use crate::difficulty::Difficulty;
+    use crate::lexer::{AdverbKind, VerbKind};
+    use crate::status::StatusKind;
+
+    fn game() -> Game {
+        let mut game = Game::with_seed(1);
+        game.state.health = 100;
+        game
+    }
+
+    #[test]
+    fn coat_resists_cold_but_not_fire() {
+        let mut game = game();
+        game.state.equipment.insert(Equipment::Coat);
+        assert_eq!(game.do_damage(DamageKind::Cold, 10), 2);
+        assert_eq!(game.do_damage(DamageKind::Fire, 10), 10);
+        assert_eq!(game.state.health, 88);
+        assert_eq!(game.state.damage_taken, 12);
+    }
+
+    #[test]
+    fn status_penalties_reduce_resistance() {
+        let mut game = game();
+        game.state.equipment.insert(Equipment::Coat);
+        game.inflict(StatusKind::Soaked, 3);
+        assert_eq!(game.state.resistance(DamageKind::Cold), 20);
+        // Penalties never push resistance below zero
+        assert_eq!(game.state.resistance(DamageKind::Fire), 0);
+    }
+
+    #[test]
+    fn difficulty_and_action_scale_damage() {
+        let mut game = game();
+        game.state.difficulty = Difficulty::Hard;
+        assert_eq!(game.damage_for(DamageKind::Physical, 10), 15);
+        game.action = ActionCost::of(VerbKind::Walk, Some(AdverbKind::Careful));
+        assert_eq!(game.damage_for(DamageKind::Physical, 10), 7);
+    }
+
+    #[test]
+    fn death_picks_an_ending() {
+        let mut game = Game::with_seed(1);
+        game.do_damage(DamageKind::Fire, 100);
+        assert!(game.state.is_dead());
+        assert_eq!(game.state.ending, Some(Ending::of_death(DamageKind::Fire, &game.state)));
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::clock::START_TIME;
+use crate::game::Game;
+use crate::ui::print;
+
+/// Presets that tune how forgiving a run is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// Minutes between the start of the run and the start of class
+    pub fn time_budget(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 90,
+            Difficulty::Normal => 60,
+            Difficulty::Hard => 45,
+        }
+    }
+
+    /// Minutes left at which the player is warned that class is starting
+    /// soon
+    pub fn time_warnings(&self) -> &'static [i32] {
+        match self {
+            Difficulty::Easy => &[45, 30, 15, 5],
+            Difficulty::Normal => &[30, 15, 5],
+            Difficulty::Hard => &[15, 5],
+        }
+    }
+
+    /// Percentage of incoming damage taken
+    pub fn damage_percent(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 50,
+            Difficulty::Normal => 100,
+            Difficulty::Hard => 150,
+        }
+    }
+
+    /// Hints available per run; `None` is unlimited
+    pub fn hint_limit(&self) -> Option<u32> {
+        match self {
+            Difficulty::Easy => None,
+            Difficulty::Normal => Some(3),
+            Difficulty::Hard => Some(1),
+        }
+    }
+
+    /// Whether hints cost points; they are free on easy
+    pub fn charges_hints(&self) -> bool {
+        !matches!(self, Difficulty::Easy)
+    }
+
+    /// Minutes wasted on a command the game does not understand
+    pub fn unknown_command_minutes(&self) -> u32 {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => 0,
+            Difficulty::Hard => 2,
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "easy" | "e" | "1" => Ok(Difficulty::Easy),
+            "normal" | "n" | "2" => Ok(Difficulty::Normal),
+            "hard" | "h" | "3" => Ok(Difficulty::Hard),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Game {
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.state.difficulty = difficulty;
+        self.state.class_time = START_TIME.after(difficulty.time_budget());
+        self.time_warnings = difficulty.time_warnings().to_vec();
+    }
+
+    /// Charges the time an unknown command wastes on harder difficulties
+    pub fn waste_time(&mut self) {
+        let minutes = self.state.difficulty.unknown_command_minutes();
+        if minutes > 0 && self.stage.is_on_the_clock() {
+            print(&format!("You waste {minutes} minutes figuring out what you meant...\n"));
+            self.pass_time(minutes);
+        }
+    }
+
+    /// Uses up a hint; false once the difficulty allows no more
+    pub fn take_hint(&mut self) -> bool {
+        if !self.stage.is_on_the_clock() {
+            return true;
+        }
+        let limit = self.state.difficulty.hint_limit();
+        if limit.is_some_and(|limit| self.state.hints_used >= limit) {
+            print(&format!(
+                "No hints left on {} difficulty; you're on your own...\n",
+                self.state.difficulty
+            ));
+            return false;
+        }
+        self.state.hints_used += 1;
+        true
+    }
+}
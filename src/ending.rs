@@ -0,0 +1,138 @@
+use crate::damage::DamageKind;
+use crate::game::Game;
+use crate::stage::State;
+use crate::status::StatusKind;
+use crate::ui::print;
+
+/// The different ways a run can end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ending {
+    Victory,
+    Frozen,
+    Burned,
+    Slain,
+    BledOut,
+    Late,
+    Declined,
+}
+
+pub const ENDINGS: &[Ending] = &[
+    Ending::Victory,
+    Ending::Frozen,
+    Ending::Burned,
+    Ending::Slain,
+    Ending::BledOut,
+    Ending::Late,
+    Ending::Declined,
+];
+
+impl Ending {
+    /// The ending for dying to `kind` of damage in `state`
+    pub fn of_death(kind: DamageKind, state: &State) -> Self {
+        match kind {
+            DamageKind::Cold => Ending::Frozen,
+            DamageKind::Fire => Ending::Burned,
+            DamageKind::Physical if state.has_status(StatusKind::Bleeding) => Ending::BledOut,
+            DamageKind::Physical => Ending::Slain,
+        }
+    }
+
+    /// Stable name stored in the profile
+    pub fn id(&self) -> &'static str {
+        match self {
+            Ending::Victory => "victory",
+            Ending::Frozen => "frozen",
+            Ending::Burned => "burned",
+            Ending::Slain => "slain",
+            Ending::BledOut => "bled-out",
+            Ending::Late => "late",
+            Ending::Declined => "declined",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Ending::Victory => "Dragonslayer",
+            Ending::Frozen => "Frozen Solid",
+            Ending::Burned => "Up in Smoke",
+            Ending::Slain => "Dragon Chow",
+            Ending::BledOut => "A Flesh Wound",
+            Ending::Late => "Tardy",
+            Ending::Declined => "Never Left the House",
+        }
+    }
+
+    pub fn epitaph(&self) -> &'static str {
+        match self {
+            Ending::Victory => "The dragon is slain and you still made it to class.",
+            Ending::Frozen => "Found in the spring thaw, still facing campus.",
+            Ending::Burned => "The bus got to campus before you did, in a way.",
+            Ending::Slain => "The dragon did not even need to chew.",
+            Ending::BledOut => "It was only a scratch. Then it was several.",
+            Ending::Late => "Survived the Keweenaw, but not the attendance policy.",
+            Ending::Declined => "The safest adventure is the one never started.",
+        }
+    }
+
+    /// The epitaph for a run that ended in `state`; slaying the dragon after
+    /// class began is not quite making it to class
+    pub fn epitaph_for(&self, state: &State) -> &'static str {
+        match self {
+            Ending::Victory if state.time_left() <= 0 => {
+                "The dragon is slain, though class started without you."
+            }
+            ending => ending.epitaph(),
+        }
+    }
+}
+
+impl Game {
+    /// Ends the run with `ending`, recording it in the profile
+    pub fn reach_ending(&mut self, ending: Ending) {
+        self.state.ending = Some(ending);
+        print(&format!("*{}*\n", ending.title()));
+        print(&format!("{{cyan}}\"{}\"{{/}}\n", ending.epitaph_for(&self.state)));
+        if self.profile.endings.insert(ending.id().to_string()) {
+            print("{yellow}New ending found!{/} Type 'endings' to see them all.\n");
+            if let Err(e) = self.profile.save() {
+                print(&format!("Could not save your profile: {e}\n"));
+            }
+        }
+    }
+
+    pub fn print_endings(&self) {
+        let found = ENDINGS
+            .iter()
+            .filter(|ending| self.profile.endings.contains(ending.id()))
+            .count();
+        print(&format!("*Endings* ({found}/{})\n", ENDINGS.len()));
+        for ending in ENDINGS {
+            if self.profile.endings.contains(ending.id()) {
+                print(&format!("  *{}* - {}\n", ending.title(), ending.epitaph()));
+            } else {
+                let redacted: String = ending
+                    .title()
+                    .chars()
+                    .map(|c| if c == ' ' { ' ' } else { '?' })
+                    .collect();
+                print(&format!("  {redacted}\n"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::Clock;
+
+    #[test]
+    fn late_victory_is_not_making_it_to_class() {
+        let mut state = State::default();
+        assert_eq!(Ending::Victory.epitaph_for(&state), Ending::Victory.epitaph());
+        state.clock = state.class_time.after(1);
+        assert_ne!(Ending::Victory.epitaph_for(&state), Ending::Victory.epitaph());
+        state.clock = Clock::at(0, 0);
+        assert_eq!(Ending::Frozen.epitaph_for(&state), Ending::Frozen.epitaph());
+    }
+}
@@ -1,16 +1,44 @@
-use std::collections::HashSet;
+use thiserror::Error;
 
-use ncurses::*;
+use crate::difficulty::Difficulty;
+use crate::choice::Choice;
+use crate::command;
+use crate::profile::Profile;
+use crate::rng::{random_seed, Rng};
+use crate::save::Snapshot;
+use crate::transcript::{self, Format};
+use crate::ui::{self, print};
 
-use crate::{lexer::{NounKind, VerbKind}, parser::{Expr, GameExpr, ParseErr, Parser, ProgramExpr}, stage::{Stage, State}};
+use crate::{action::ActionCost, lexer::{NounKind, VerbKind}, parser::{Expr, GameExpr, ParseErr, Parser, ProgramExpr}, stage::{Stage, State}};
 
 #[derive(Debug)]
 pub struct Game {
     pub is_running: bool,
     pub parse_mode: ParseMode,
-    pub name: String,
     pub stage: Stage,
     pub state: State,
+    /// Minutes left at which to warn the player, set by the difficulty
+    pub time_warnings: Vec<i32>,
+    /// Cost of the action currently being performed
+    pub action: ActionCost,
+    /// Seed the run was started with; the same seed and input always play
+    /// out the same way
+    pub seed: u64,
+    pub rng: Rng,
+    pub profile: Profile,
+    /// Difficulty picked on the command line, skipping the prompt
+    pub difficulty: Option<Difficulty>,
+    /// Describe the scene again after actions that stay in it
+    pub verbose: bool,
+    /// Lines entered since the run began, replayed to load a save
+    pub history: Vec<String>,
+    /// RNG as it was when the run began
+    pub history_rng: Rng,
+    pub snapshots: Vec<Snapshot>,
+    /// Where a lost run can be retried from
+    pub checkpoint: Option<Snapshot>,
+    /// Options of the choice prompt being shown
+    pub choices: Vec<Choice>,
 }
 
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
@@ -19,9 +47,22 @@ pub enum Equipment {
     Sword
 }
 
-#[derive(Clone, Debug)]
+#[derive(Error, Clone, Debug)]
 pub enum GameErr {
+    #[error("{0}")]
     Parse(ParseErr),
+
+    #[error("That doesn't work here; try 'help' or 'hint'")]
+    UnexpectedInput,
+
+    #[error("{0}")]
+    InvalidName(&'static str),
+
+    #[error("There is no checkpoint to go back to yet")]
+    NoCheckpoint,
+
+    #[error("You can't exit the {0:?}; try 'exit game'")]
+    UnsupportedProgram(NounKind),
 }
 
 pub type Result<T> = std::result::Result<T, GameErr>;
@@ -32,99 +73,160 @@ impl From<ParseErr> for GameErr {
     }
 }
 
-const HELP: &str = include_str!("../help.txt");
+pub const HELP: &str = include_str!("../help.txt");
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ParseMode {
     Grammar,
     Raw,
     Confirm,
+    Choice,
 }
 
 pub fn msg(s: &str) {
-    clear();
-    addstr(&format!("{s}\nPress any key to continue...\n"));
-    getch();
+    print(s);
+    if !s.ends_with('\n') {
+        print("\n");
+    }
+    ui::wait_key("Press any key to continue...");
 }
 
 pub fn oops() {
     msg("Can't use that here; try 'help' or 'hint'\n");
 }
 
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Game {
     pub fn new() -> Self {
+        Self::with_seed(random_seed())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
         Self {
             is_running: true,
             parse_mode: ParseMode::Grammar,
-            name: "Jeff".to_string(),
             stage: Stage::First,
             state: Default::default(),
+            time_warnings: Difficulty::default().time_warnings().to_vec(),
+            action: ActionCost::default(),
+            seed,
+            rng: Rng::new(seed),
+            profile: Profile::default(),
+            difficulty: None,
+            verbose: true,
+            history: Vec::new(),
+            history_rng: Rng::new(seed),
+            snapshots: Vec::new(),
+            checkpoint: None,
+            choices: Vec::new(),
         }
     }
 
-    pub fn print_help(&mut self) {
-        clear();
-        addstr(&format!("{HELP}\n\nPress any key to continue..."));
-        getch();
-        refresh();
-        self.transition(self.stage);
-    }
-
     pub fn is_running(&self) -> bool {
         self.is_running
     }
 
-    pub fn equip_armor(&mut self, armor: Equipment, value: u32, s: &str) {
-        if self.state.equipment.insert(armor) {
+    pub fn equip(&mut self, item: Equipment, s: &str) {
+        if self.state.equipment.insert(item) {
             msg(s);
-            self.state.armor += value;
         } else {
             msg("You already have this equipt");
         }
     }
 
-    fn eval_program_exit(&mut self, program: ProgramExpr) {
+    /// Checks that work in every stage and take no time
+    fn eval_check(&mut self, verb: VerbKind, noun: NounKind) -> bool {
+        match (verb, noun) {
+            (VerbKind::Check, NounKind::Stats) => {
+                self.print_stats();
+                true
+            }
+            (VerbKind::Check, NounKind::Time) => {
+                self.print_clock();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn eval_program_exit(&mut self, program: ProgramExpr) -> Result<()> {
         match program.noun() {
             NounKind::Game => self.is_running = false,
-            _ => unimplemented!(),
+            noun => return Err(GameErr::UnsupportedProgram(noun)),
         }
+        Ok(())
     }
 
-    fn eval_program(&mut self, program: ProgramExpr) {
+    fn eval_program(&mut self, program: ProgramExpr) -> Result<()> {
         match program.verb() {
             VerbKind::Exit => self.eval_program_exit(program),
-            _ => unimplemented!()
+            _ => Err(GameErr::UnsupportedProgram(program.noun())),
         }
     }
 
-    fn eval_game_over(&mut self, game: GameExpr) -> Stage {
-        let confirm = match game {
-            GameExpr::Confirm(b) => b,
-            _ => unreachable!(),
+    fn eval_game_over(&mut self, game: GameExpr) -> Result<Stage> {
+        let GameExpr::Choice(choice) = game else {
+            return Err(GameErr::UnexpectedInput);
         };
 
-        if confirm {
-            Stage::First
-        } else {
-            Stage::Quit
+        match choice {
+            "retry" => self.retry().ok_or(GameErr::NoCheckpoint),
+            "restart" => {
+                self.reset_run();
+                Ok(Stage::Library)
+            }
+            "quit" => Ok(Stage::Quit),
+            _ => Err(GameErr::UnexpectedInput),
         }
     }
 
 
-    pub fn eval_finish(&mut self, game: GameExpr) -> Stage {
+    pub fn eval_finish(&mut self, game: GameExpr) -> Result<Stage> {
         let confirm = match game {
             GameExpr::Confirm(b) => b,
-            _ => unreachable!(),
+            _ => return Err(GameErr::UnexpectedInput),
         };
 
         if confirm {
-            Stage::First
+            Ok(Stage::First)
         } else {
-            Stage::Quit
+            Ok(Stage::Quit)
         }
     }
 
     fn eval_game(&mut self, game: GameExpr) {
+        if let GameExpr::Svn { verb, noun, adverb, .. } = game {
+            if self.eval_check(verb, noun) {
+                return;
+            }
+            self.snapshot();
+            self.tick_status();
+            if self.state.is_dead() {
+                self.transition(Stage::GameOver);
+                return;
+            }
+
+            let cost = ActionCost::of(verb, adverb);
+            self.pass_time(cost.minutes);
+            if cost.fails(&self.state, &mut self.rng) {
+                msg("You try to rush, but your body refuses to keep up...\n");
+                self.transition(self.stage);
+                return;
+            }
+            self.action = cost;
+
+            if self.eval_cure(verb, noun) {
+                self.action = ActionCost::default();
+                self.transition(self.stage);
+                return;
+            }
+        }
+
         let next_stage = match self.stage {
             Stage::First => {
                 self.eval_first(game)
@@ -132,6 +234,15 @@ impl Game {
             Stage::PlayConfirm => {
                 self.eval_playconfirm(game)
             }
+            Stage::ChoosePronouns => {
+                self.eval_choose_pronouns(game)
+            }
+            Stage::ChooseBackground => {
+                self.eval_choose_background(game)
+            }
+            Stage::ChooseDifficulty => {
+                self.eval_choose_difficulty(game)
+            }
             Stage::Library => {
                 self.eval_library(game)
             }
@@ -139,12 +250,12 @@ impl Game {
                 self.eval_outside_library(game)
             }
             Stage::TransitOnFoot => {
-                Stage::CampusDragon
+                Ok(Stage::CampusDragon)
             }
             Stage::BusArrive => {
                 self.eval_bus_arrive(game)
             }
-            Stage::GameOver => {
+            Stage::GameOver | Stage::LateForClass => {
                 self.eval_game_over(game)
             }
             Stage::BusFire => {
@@ -157,27 +268,76 @@ impl Game {
                 self.eval_strike_dragon(game)
             }
             Stage::Quit => {
-                Stage::Quit
+                Ok(Stage::Quit)
             }
             Stage::Finish => {
                 self.eval_finish(game)
             },
         };
-        self.transition(next_stage);
+        self.action = ActionCost::default();
+        let next_stage = match next_stage {
+            Ok(stage) => stage,
+            Err(e) => {
+                print(&format!("{e}\n"));
+                return;
+            }
+        };
+
+        if self.state.is_dead() && !matches!(next_stage, Stage::First | Stage::Quit) {
+            self.transition(Stage::GameOver);
+        } else {
+            self.transition(next_stage);
+        }
+    }
+
+    /// Starts or stops recording a transcript; `args` may name a format
+    pub fn eval_script(&mut self, args: &str) {
+        let format = match args.trim().to_ascii_lowercase().as_str() {
+            "" => Format::Text,
+            name => match Format::from_name(name) {
+                Some(format) => format,
+                None => {
+                    print("Try: 'script', 'script markdown' or 'script html'\n");
+                    return;
+                }
+            },
+        };
+        let path = transcript::default_path(format);
+        match transcript::start(&path, format) {
+            Ok(()) => print(&format!("Recording a transcript to '{}'\n", path.display())),
+            Err(e) => print(&format!("Could not start a transcript: {e}\n")),
+        }
+    }
+
+    pub fn eval_unscript(&mut self) {
+        if transcript::is_recording() {
+            print("Transcript stopped\n");
+            transcript::stop();
+        } else {
+            print("No transcript is being recorded\n");
+        }
     }
 
     pub fn eval(&mut self, s: &str) {
-        match s.trim().to_ascii_lowercase().as_str() {
-            "help" => {
-                self.print_help();
-                return;
-            }
-            "hint" => {
-                self.print_hint();
+        self.eval_input(s);
+        self.check_achievements();
+    }
+
+    fn eval_input(&mut self, s: &str) {
+        let command = s.trim();
+        if !command.is_empty() {
+            self.state.commands += 1;
+        }
+        if let Some((meta, args)) = command::lookup(command) {
+            if self.eval_meta(meta, args) {
+                if meta.saved {
+                    self.history.push(s.to_string());
+                }
                 return;
             }
-            _ => ()
         }
+        // Anything else, such as a name that looks like a command, replays
+        self.history.push(s.to_string());
 
         let expr = {
             match self.parse_mode {
@@ -186,29 +346,40 @@ impl Game {
                 }
                 ParseMode::Grammar => {
                     let mut parser = Parser::new(s);
-                    match parser.next() {
+                    match parser.parse_next() {
                         Ok(Some(v)) => v,
                         Ok(None) => return,
                         Err(ParseErr::Unexpected((_, _))) => {
-                            addstr("Unknown command, try 'help' or 'hint'\n");
+                            print("Unknown command, try 'help' or 'hint'\n");
+                            self.waste_time();
                             return;
                         }
                         Err(ParseErr::Unimplemented) => {
                             return;
                         }
                         Err(e) => {
-                            addstr(e.to_string().as_str());
-                            addstr("\n");
+                            print(e.to_string().as_str());
+                            print("\n");
                             return;
                         }
                     }
                 }
+                ParseMode::Choice => match self.choose(s) {
+                    Some(choice) => Expr::Game(GameExpr::Choice(choice)),
+                    None => {
+                        print(&format!(
+                            "Invalid option: pick a number from 1 to {}\n",
+                            self.choices.len()
+                        ));
+                        return;
+                    }
+                },
                 ParseMode::Confirm => {
                     match s.trim().to_ascii_lowercase().as_str() {
                         "yes" | "true" | "ok" => Expr::Game(GameExpr::Confirm(true)),
                         "no" | "false" | "nope" => Expr::Game(GameExpr::Confirm(false)),
                         _ => {
-                            addstr("Invalid option: try yes or no\n");
+                            print("Invalid option: try yes or no\n");
                             return
                         }
                     }
@@ -221,11 +392,17 @@ impl Game {
                 self.eval_game(game);
             }
             Expr::Program(program) => {
-                self.eval_program(program);
+                if let Err(e) = self.eval_program(program) {
+                    print(&format!("{e}\n"));
+                }
+            }
+            Expr::Confirm(confirm) => self.eval_game(GameExpr::Confirm(confirm)),
+            Expr::Help => self.eval_help(""),
+            Expr::Hint => {
+                if self.take_hint() {
+                    self.print_hint();
+                }
             }
-            Expr::Help => self.print_help(),
-            Expr::Hint => todo!(),
-            _ => unimplemented!(),
         }
     }
 }
@@ -34,6 +34,8 @@ pub enum NounKind {
     Sword,
     Dragon,
     Coat,
+    Snow,
+    Time,
 
     Dummy,
 }
@@ -58,6 +60,8 @@ pub enum VerbKind {
     Run,
     Equip,
     Strike,
+    Roll,
+    Remove,
 
     Dummy,
 }
@@ -94,15 +98,250 @@ pub enum TokenKind {
     Dummy,
 }
 
-impl Into<TokenKind> for VerbKind {
-    fn into(self) -> TokenKind {
-        TokenKind::Verb(self)
+impl From<VerbKind> for TokenKind {
+    fn from(verb: VerbKind) -> Self {
+        TokenKind::Verb(verb)
     }
 }
 
-impl Into<TokenKind> for NounKind {
-    fn into(self) -> TokenKind {
-        TokenKind::Noun(self)
+impl From<NounKind> for TokenKind {
+    fn from(noun: NounKind) -> Self {
+        TokenKind::Noun(noun)
+    }
+}
+
+/// A verb the parser understands, and what `help <verb>` says about it
+#[derive(Debug)]
+pub struct Verb {
+    pub kind: VerbKind,
+    /// The first word is the verb's name; the rest are synonyms
+    pub words: &'static [&'static str],
+    pub summary: &'static str,
+    /// Every object the game responds to this verb being used on should
+    /// appear in one of these, as `help <verb>` lists objects from them
+    pub examples: &'static [&'static str],
+}
+
+impl Verb {
+    /// The nouns the examples use this verb on, in order
+    pub fn objects(&self) -> Vec<NounKind> {
+        let mut objects = Vec::new();
+        for example in self.examples {
+            let mut lexer = Lexer::new(example);
+            loop {
+                match lexer.next_token().kind() {
+                    TokenKind::Eof => break,
+                    TokenKind::Noun(noun) if !objects.contains(&noun) => objects.push(noun),
+                    _ => (),
+                }
+            }
+        }
+        objects
+    }
+}
+
+pub const VERBS: &[Verb] = &[
+    Verb {
+        kind: VerbKind::Go,
+        words: &["go"],
+        summary: "Sets off somewhere on foot",
+        examples: &["go to campus"],
+    },
+    Verb {
+        kind: VerbKind::Walk,
+        words: &["walk"],
+        summary: "Sets off somewhere on foot",
+        examples: &["walk to campus", "walk to campus quick"],
+    },
+    Verb {
+        kind: VerbKind::Head,
+        words: &["head"],
+        summary: "Sets off somewhere on foot",
+        examples: &["head to campus"],
+    },
+    Verb {
+        kind: VerbKind::Leave,
+        words: &["leave"],
+        summary: "Leaves a place",
+        examples: &["leave the building", "leave the building slow", "leave the back"],
+    },
+    Verb {
+        kind: VerbKind::Run,
+        words: &["run"],
+        summary: "Runs, away or out",
+        examples: &["run away", "run to the back"],
+    },
+    Verb {
+        kind: VerbKind::Exit,
+        words: &["exit"],
+        summary: "Gets out of something, or out of the game",
+        examples: &["exit the back", "exit game"],
+    },
+    Verb {
+        kind: VerbKind::Enter,
+        words: &["enter"],
+        summary: "Goes into something",
+        examples: &["enter the bathroom", "enter the bus"],
+    },
+    Verb {
+        kind: VerbKind::Board,
+        words: &["board"],
+        summary: "Gets on a vehicle",
+        examples: &["board the bus"],
+    },
+    Verb {
+        kind: VerbKind::Wait,
+        words: &["wait"],
+        summary: "Waits for something to happen",
+        examples: &["wait for the bus"],
+    },
+    Verb {
+        kind: VerbKind::Do,
+        words: &["do"],
+        summary: "Mostly useful for doing nothing",
+        examples: &["do nothing"],
+    },
+    Verb {
+        kind: VerbKind::Equip,
+        words: &["equip"],
+        summary: "Puts on or picks up an item",
+        examples: &["equip the coat", "equip the sword"],
+    },
+    Verb {
+        kind: VerbKind::Remove,
+        words: &["remove", "unequip"],
+        summary: "Takes off an item",
+        examples: &["remove the coat"],
+    },
+    Verb {
+        kind: VerbKind::Strike,
+        words: &["strike", "hit"],
+        summary: "Attacks something, or with something",
+        examples: &["strike the dragon", "strike with the sword"],
+    },
+    Verb {
+        kind: VerbKind::Roll,
+        words: &["roll"],
+        summary: "Rolls around in something",
+        examples: &["roll in the snow"],
+    },
+    Verb {
+        kind: VerbKind::Check,
+        words: &["check"],
+        summary: "Looks up your stats or the time",
+        examples: &["check stats", "check the time"],
+    },
+    Verb {
+        kind: VerbKind::Quit,
+        words: &["quit"],
+        summary: "Quits the game",
+        examples: &["quit game"],
+    },
+    Verb {
+        kind: VerbKind::Close,
+        words: &["close"],
+        summary: "Closes something",
+        examples: &["close game"],
+    },
+    Verb {
+        kind: VerbKind::Get,
+        words: &["get"],
+        summary: "Picks something up",
+        examples: &[],
+    },
+    Verb {
+        kind: VerbKind::Open,
+        words: &["open"],
+        summary: "Opens something",
+        examples: &[],
+    },
+    Verb {
+        kind: VerbKind::Continue,
+        words: &["continue"],
+        summary: "Keeps going",
+        examples: &[],
+    },
+];
+
+pub const NOUNS: &[(NounKind, &[&str])] = &[
+    (NounKind::Chair, &["chair"]),
+    (NounKind::Door, &["door"]),
+    (NounKind::Stair, &["stair"]),
+    (NounKind::Weapon, &["weapon"]),
+    (NounKind::Homework, &["homework"]),
+    (NounKind::Computer, &["computer"]),
+    (NounKind::Game, &["game"]),
+    (NounKind::Building, &["building"]),
+    (NounKind::Stats, &["stats"]),
+    (NounKind::Foot, &["foot"]),
+    (NounKind::Bus, &["bus"]),
+    (NounKind::Back, &["back"]),
+    (NounKind::Nothing, &["nothing"]),
+    (NounKind::Bathroom, &["bathroom"]),
+    (NounKind::Campus, &["campus"]),
+    (NounKind::Away, &["away"]),
+    (NounKind::Sword, &["sword"]),
+    (NounKind::Dragon, &["dragon"]),
+    (NounKind::Coat, &["coat"]),
+    (NounKind::Snow, &["snow"]),
+    (NounKind::Time, &["time", "clock"]),
+];
+
+pub const ADVERBS: &[(AdverbKind, &[&str])] = &[
+    (AdverbKind::Quick, &["quick"]),
+    (AdverbKind::Slow, &["slow"]),
+    (AdverbKind::Careful, &["careful"]),
+    (AdverbKind::Intense, &["intense"]),
+];
+
+pub const ADJECTIVES: &[(AdjKind, &[&str])] = &[
+    (AdjKind::Near, &["near"]),
+    (AdjKind::Far, &["far"]),
+    (AdjKind::Strong, &["strong"]),
+    (AdjKind::Weak, &["weak"]),
+];
+
+/// Filler words the parser skips
+pub const ARTICLES: &[&str] = &["the", "a", "an", "for", "to", "with", "in", "on"];
+
+fn find<K: Copy>(table: &[(K, &[&str])], word: &str) -> Option<K> {
+    table
+        .iter()
+        .find(|(_, words)| words.contains(&word))
+        .map(|&(kind, _)| kind)
+}
+
+/// The word `noun` is written as
+pub fn noun_name(noun: NounKind) -> &'static str {
+    NOUNS
+        .iter()
+        .find(|&&(kind, _)| kind == noun)
+        .map_or("?", |(_, words)| words[0])
+}
+
+/// The verb `word` names, if any
+pub fn find_verb(word: &str) -> Option<&'static Verb> {
+    VERBS.iter().find(|verb| verb.words.contains(&word))
+}
+
+fn lookup(word: &str) -> TokenKind {
+    match word {
+        "help" => return TokenKind::Help,
+        "hint" => return TokenKind::Hint,
+        _ => (),
+    }
+    if let Some(verb) = find_verb(word) {
+        TokenKind::Verb(verb.kind)
+    } else if let Some(noun) = find(NOUNS, word) {
+        TokenKind::Noun(noun)
+    } else if let Some(adverb) = find(ADVERBS, word) {
+        TokenKind::Adverb(adverb)
+    } else if let Some(adj) = find(ADJECTIVES, word) {
+        TokenKind::Adj(adj)
+    } else if ARTICLES.contains(&word) {
+        TokenKind::Article
+    } else {
+        TokenKind::Illegal
     }
 }
 
@@ -138,73 +377,7 @@ impl<'a> Lexer<'a> {
             word.push(self.bump().to_ascii_lowercase());
         }
 
-        match word.as_str() {
-            "help" => TokenKind::Help,
-            "hint" => TokenKind::Hint,
-
-            // Verbs
-            "get" => TokenKind::Verb(VerbKind::Get),
-            "open" => TokenKind::Verb(VerbKind::Open),
-            "close" => TokenKind::Verb(VerbKind::Close),
-            "exit" => TokenKind::Verb(VerbKind::Exit),
-            "go" => TokenKind::Verb(VerbKind::Go),
-            "quit" => TokenKind::Verb(VerbKind::Quit),
-            "leave" => TokenKind::Verb(VerbKind::Leave),
-            "check" => TokenKind::Verb(VerbKind::Check),
-            "continue" => TokenKind::Verb(VerbKind::Continue),
-            "wait" => TokenKind::Verb(VerbKind::Wait),
-            "board" => TokenKind::Verb(VerbKind::Board),
-            "enter" => TokenKind::Verb(VerbKind::Enter),
-            "head" => TokenKind::Verb(VerbKind::Head),
-            "do" => TokenKind::Verb(VerbKind::Do),
-            "walk" => TokenKind::Verb(VerbKind::Walk),
-            "run" => TokenKind::Verb(VerbKind::Run),
-            "equip" => TokenKind::Verb(VerbKind::Equip),
-            "hit" | "strike" => TokenKind::Verb(VerbKind::Strike),
-
-            // Nouns
-            "chair" => TokenKind::Noun(NounKind::Chair),
-            "door" => TokenKind::Noun(NounKind::Door),
-            "stair" => TokenKind::Noun(NounKind::Stair),
-            "weapon" => TokenKind::Noun(NounKind::Weapon),
-            "homework" => TokenKind::Noun(NounKind::Homework),
-            "computer" => TokenKind::Noun(NounKind::Computer),
-            "game" => TokenKind::Noun(NounKind::Game),
-            "building" => TokenKind::Noun(NounKind::Building),
-            "stats" => TokenKind::Noun(NounKind::Stats),
-            "foot" => TokenKind::Noun(NounKind::Foot),
-            "bus" => TokenKind::Noun(NounKind::Bus),
-            "back" => TokenKind::Noun(NounKind::Back),
-            "nothing" => TokenKind::Noun(NounKind::Nothing),
-            "bathroom" => TokenKind::Noun(NounKind::Bathroom),
-            "campus" => TokenKind::Noun(NounKind::Campus),
-            "away" => TokenKind::Noun(NounKind::Away),
-            "sword" => TokenKind::Noun(NounKind::Sword),
-            "dragon" => TokenKind::Noun(NounKind::Dragon),
-            "coat" => TokenKind::Noun(NounKind::Coat),
-
-            // Adverbs
-            "quick" => TokenKind::Adverb(AdverbKind::Quick),
-            "slow" => TokenKind::Adverb(AdverbKind::Slow),
-            "careful" => TokenKind::Adverb(AdverbKind::Careful),
-            "intense" => TokenKind::Adverb(AdverbKind::Intense),
-
-            // Adjectives
-            "near" => TokenKind::Adj(AdjKind::Near),
-            "far" => TokenKind::Adj(AdjKind::Far),
-            "strong" => TokenKind::Adj(AdjKind::Strong),
-            "weak" => TokenKind::Adj(AdjKind::Weak),
-
-            // Articles
-            "the" => TokenKind::Article,
-            "a" => TokenKind::Article,
-            "an" => TokenKind::Article,
-            "for" => TokenKind::Article,
-            "to" => TokenKind::Article,
-            "with" => TokenKind::Article,
-
-            _ => TokenKind::Illegal,
-        }
+        lookup(&word)
     }
 
     pub fn next_token(&mut self) -> Token {
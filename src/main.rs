@@ -1,112 +1,174 @@
+use args::Args;
 use game::Game;
-use lexer::Lexer;
-#[allow(unused_imports)]
-use ncurses::*;
 use parser::Parser;
+use profile::Profile;
+use replay::{Recorder, Replay};
+use rng::random_seed;
 use stage::Stage;
-
-use crate::lexer::TokenKind;
-
+use std::thread;
+use std::time::Duration;
+use transcript::Format;
+use ui::Typewriter;
+
+pub mod achievement;
+pub mod action;
+pub mod args;
+pub mod character;
+pub mod checkpoint;
+pub mod choice;
+pub mod clock;
+pub mod command;
+pub mod crash;
+pub mod damage;
+pub mod difficulty;
+pub mod ending;
 pub mod game;
 pub mod lexer;
+pub mod markup;
 pub mod parser;
+pub mod profile;
+pub mod replay;
+pub mod rng;
+pub mod save;
+pub mod score;
 pub mod stage;
+pub mod status;
+pub mod template;
+pub mod transcript;
+pub mod ui;
 
 fn _test_parser() {
     let src = r#"open the door"#;
 
     let mut parser = Parser::new(src);
 
-    loop {
-        let expr = match parser.next().unwrap() {
-            Some(t) => t,
-            None => break,
-        };
+    while let Some(expr) = parser.parse_next().unwrap() {
         dbg!(expr);
     }
 }
 
-fn main() {
-    initscr();
-
-    cbreak();
-    noecho();
-    initscr();
-    scrollok(stdscr(), true);
-    keypad(stdscr(), true);
+/// Exits with `message`, leaving curses first so the terminal is usable
+fn fail(message: String) -> ! {
+    ui::end();
+    eprintln!("{message}");
+    std::process::exit(1);
+}
 
-    let mut s = String::new();
-    let mut x = 0;
-    let mut y = 0;
+/// Next line of input, from the recording being replayed until it runs out
+/// and from the player after that
+fn next_input(args: &Args, replay: &mut Option<Replay>, game: &Game) -> Option<String> {
+    let Some(lines) = replay else {
+        return ui::read_line();
+    };
+
+    match lines.next_line() {
+        Some(line) => {
+            if args.step {
+                ui::set_pauses(true);
+                ui::wait_key(&format!("Replay: {} lines left, press any key...", lines.remaining() + 1));
+                ui::set_pauses(false);
+            }
+            thread::sleep(Duration::from_millis(args.replay_delay));
+            ui::echo_input(&line);
+            Some(line)
+        }
+        None => {
+            *replay = None;
+            ui::set_pauses(true);
+            set_typewriter(args);
+            ui::print(&format!("*Replay finished at {:?}*\n", game.stage));
+            ui::read_line()
+        }
+    }
+}
 
-    let mut max_x = 0;
-    let mut max_y = 0;
+fn set_typewriter(args: &Args) {
+    if args.typewriter {
+        ui::set_typewriter(Some(Typewriter {
+            char_delay: Duration::from_millis(1000 / args.text_speed.max(1)),
+            line_pause: Duration::from_millis(args.line_pause),
+        }));
+    }
+}
 
-    let mut game = Game::new();
+fn main() {
+    let args = Args::parse();
+    crash::install_hook();
+
+    let mut replay = args.replay.as_deref().map(|path| {
+        Replay::load(path)
+            .unwrap_or_else(|e| fail(format!("Could not read recording '{}': {e}", path.display())))
+    });
+    let mut recorder = args.record.as_deref().map(|path| {
+        Recorder::create(path)
+            .unwrap_or_else(|e| fail(format!("Could not write recording '{}': {e}", path.display())))
+    });
+    if let Some(path) = &args.script {
+        if let Err(e) = transcript::start(path, Format::from_path(path)) {
+            fail(format!("Could not write transcript to '{}': {e}", path.display()));
+        }
+    }
 
-    game.transition(Stage::First);
+    // Someone else's replayed session must not unlock anything on the
+    // player's own profile, so it gets a throwaway one unless asked otherwise
+    let profile_path = match (&args.profile, &replay) {
+        (Some(path), _) => Some(path.clone()),
+        (None, Some(_)) => None,
+        (None, None) => Some(profile::default_path()),
+    };
+    let profile = match profile_path {
+        Some(path) => Profile::load(path.clone())
+            .unwrap_or_else(|e| fail(format!("Could not read profile '{}': {e}", path.display()))),
+        None => Profile::default(),
+    };
+
+    if args.plain {
+        ui::init_plain();
+    } else {
+        ui::init();
+        if replay.is_some() {
+            ui::set_pauses(false);
+        } else {
+            set_typewriter(&args);
+        }
+    }
 
-    while game.is_running() {
-        getyx(stdscr(), &mut y, &mut x);
-        getmaxyx(stdscr(), &mut max_y, &mut max_x);
+    // A replay must use the seed it was recorded with
+    let seed = replay
+        .as_ref()
+        .and_then(|replay| replay.meta("seed")?.parse().ok())
+        .or(args.seed)
+        .unwrap_or_else(random_seed);
+    // ...and skip the difficulty prompt only if the recording did
+    let difficulty = match &replay {
+        Some(replay) => replay.meta("difficulty").and_then(|d| d.parse().ok()),
+        None => args.difficulty,
+    };
+    if let Some(recorder) = &mut recorder {
+        recorder.meta("seed", &seed.to_string());
+        if let Some(difficulty) = difficulty {
+            recorder.meta("difficulty", &difficulty.to_string());
+        }
+    }
 
-        loop {
+    let mut game = Game::with_seed(seed);
+    game.profile = profile;
+    game.difficulty = difficulty;
 
-            getyx(stdscr(), &mut y, &mut x);
-            let c = getch() as u8 as char;
-            if c == '\n' {
-                y += 1;
-                wmove(stdscr(), y, 0);
-                break;
-            }
-            s.push(c);
-            x += 1;
-
-            /*
-            let c = match get_wch() {
-                Some(c) => c,
-                None => break,
-            };
-
-            match c {
-                WchResult::KeyCode(constants::KEY_LEFT) if x > 0 => {
-                    x -= 1;
-                }
-                WchResult::KeyCode(constants::KEY_RIGHT) if (x as usize) < s.len() => {
-                    x += 1;
-                }
-                WchResult::KeyCode(constants::KEY_BACKSPACE) if x > 0 => {
-                    x -= 1;
-                    s.remove(x as usize);
-                }
-                WchResult::Char(c) if char::from_u32(c).unwrap() == '\n' => {
-                    y += 1;
-                    wmove(stdscr(), y, 0);
-                    break;
-                }
-                WchResult::Char(c) => {
-                    s.insert(x as usize, char::from_u32(c).unwrap());
-                    x += 1;
-                }
-                _ => {
-                }
-            }
-            */
-            wmove(stdscr(), y, 0);
-            clrtoeol();
-            addstr(&s);
-            wmove(stdscr(), y, x);
-            refresh();
-        }
+    game.transition(Stage::First);
+    game.update_status();
 
-        if y >= max_y {
-            wmove(stdscr(), y + 1, 0);
-            scrl(10);
+    while game.is_running() && !ui::is_closed() {
+        let Some(s) = next_input(&args, &mut replay, &game) else {
+            break;
+        };
+        if let Some(recorder) = &mut recorder {
+            recorder.record(&s);
         }
-
+        crash::note_input(&s);
         game.eval(&s);
-        refresh();
-        s.clear();
+        game.update_status();
     }
-    endwin();
+    transcript::stop();
+    ui::end();
 }
@@ -0,0 +1,140 @@
+//! Lightweight inline markup for story text
+//!
+//! - `*bold*` toggles bold
+//! - `{red}flames{/}` colors text until `{/}`
+//! - `[coat]` highlights something the player can interact with
+//! - `\` makes the next character literal
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Yellow,
+    Green,
+    Cyan,
+    Blue,
+    Magenta,
+}
+
+impl Color {
+    pub const ALL: [Color; 6] = [
+        Color::Red,
+        Color::Yellow,
+        Color::Green,
+        Color::Cyan,
+        Color::Blue,
+        Color::Magenta,
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|color| color.name() == name)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Color::Red => "red",
+            Color::Yellow => "yellow",
+            Color::Green => "green",
+            Color::Cyan => "cyan",
+            Color::Blue => "blue",
+            Color::Magenta => "magenta",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Style {
+    pub bold: bool,
+    pub color: Option<Color>,
+    pub highlight: bool,
+}
+
+pub type Cell = (char, Style);
+
+/// Splits `s` into characters tagged with the style they should be drawn in
+pub fn parse(s: &str) -> Vec<Cell> {
+    let mut cells = Vec::new();
+    let mut style = Style::default();
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(c) = chars.next() {
+                    cells.push((c, style));
+                }
+            }
+            '*' => style.bold = !style.bold,
+            '[' => style.highlight = true,
+            ']' => style.highlight = false,
+            '{' => {
+                let tag: String = chars.clone().take_while(|&c| c != '}').collect();
+                let color = match tag.as_str() {
+                    "/" => Some(None),
+                    tag => Color::from_name(tag).map(Some),
+                };
+                match color {
+                    Some(color) => {
+                        style.color = color;
+                        chars.nth(tag.chars().count());
+                    }
+                    // Not a tag we know; leave it as text
+                    None => cells.push((c, style)),
+                }
+            }
+            c => cells.push((c, style)),
+        }
+    }
+    cells
+}
+
+/// Removes all markup from `s`, for frontends that cannot render it
+pub fn strip(s: &str) -> String {
+    parse(s).into_iter().map(|(c, _)| c).collect()
+}
+
+/// Escapes `s` so it renders literally, for text typed by the player
+pub fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '\\' | '*' | '[' | ']' | '{' | '}') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_styles() {
+        let cells = parse("a*b*{red}c{/}[d]");
+        let bold = Style { bold: true, ..Style::default() };
+        let red = Style { color: Some(Color::Red), ..Style::default() };
+        let highlight = Style { highlight: true, ..Style::default() };
+        assert_eq!(
+            cells,
+            vec![('a', Style::default()), ('b', bold), ('c', red), ('d', highlight)]
+        );
+    }
+
+    #[test]
+    fn unknown_tags_are_text() {
+        assert_eq!(strip("{nope}x{/}"), "{nope}x");
+    }
+
+    #[test]
+    fn escape_round_trips() {
+        for s in ["*bold*", "[coat]", "{red}", "back\\slash", "plain", "a}b{c"] {
+            assert_eq!(strip(&escape(s)), s);
+            assert!(parse(&escape(s)).iter().all(|&(_, style)| style == Style::default()));
+        }
+    }
+
+    #[test]
+    fn trailing_backslash_is_dropped() {
+        assert_eq!(strip("a\\"), "a");
+    }
+}
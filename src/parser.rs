@@ -17,6 +17,8 @@ pub enum GameExpr {
     },
     Raw(String),
     Confirm(bool),
+    /// Id of the option picked at a choice prompt
+    Choice(&'static str),
 }
 
 #[derive(Debug, Clone)]
@@ -131,15 +133,16 @@ impl<'a> Parser<'a> {
 
     fn expect_pos(&mut self) -> Result<(Option<AdjKind>, NounKind, Option<AdverbKind>)> {
         let token = self.eat_articles();
-        match token.kind() {
-            TokenKind::Noun(noun) => {
-                let adverb_token = self.expect_adverb()?;
-                Ok((None, noun, adverb_token))
-            }
-            _ => {
-                Err(ParseErr::MissingNoun)
+        let (adj, noun) = match token.kind() {
+            TokenKind::Noun(noun) => (None, noun),
+            TokenKind::Adj(adj) => {
+                let noun = self.expect_noun().map_err(|_| ParseErr::MissingNoun)?;
+                (Some(adj), noun)
             }
-        }
+            _ => return Err(ParseErr::MissingNoun),
+        };
+        let adverb = self.expect_adverb()?;
+        Ok((adj, noun, adverb))
     }
 
     fn parse_svn(&mut self, verb: VerbKind) -> Result<Option<Expr>> {
@@ -159,7 +162,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    pub fn next(&mut self) -> Result<Option<Expr>> {
+    pub fn parse_next(&mut self) -> Result<Option<Expr>> {
         let token = self.lexer.next_token();
 
         match token.kind() {
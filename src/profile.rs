@@ -0,0 +1,58 @@
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Achievements and endings that carry over between runs
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    pub path: Option<PathBuf>,
+    pub achievements: BTreeSet<String>,
+    pub endings: BTreeSet<String>,
+}
+
+/// `~/.batman_profile`, or the working directory without a home directory
+pub fn default_path() -> PathBuf {
+    match env::var_os("HOME") {
+        Some(home) => PathBuf::from(home).join(".batman_profile"),
+        None => PathBuf::from(".batman_profile"),
+    }
+}
+
+impl Profile {
+    /// Loads the profile at `path`; a missing file is an empty profile
+    pub fn load(path: PathBuf) -> io::Result<Self> {
+        let mut profile = Self {
+            path: Some(path.clone()),
+            ..Default::default()
+        };
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(profile),
+            Err(e) => return Err(e),
+        };
+        for line in contents.lines() {
+            if let Some(id) = line.strip_prefix("achievement ") {
+                profile.achievements.insert(id.trim().to_string());
+            } else if let Some(id) = line.strip_prefix("ending ") {
+                profile.endings.insert(id.trim().to_string());
+            }
+        }
+        Ok(profile)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut contents = String::from("# batman profile\n");
+        for id in &self.achievements {
+            contents.push_str(&format!("achievement {id}\n"));
+        }
+        for id in &self.endings {
+            contents.push_str(&format!("ending {id}\n"));
+        }
+        fs::write(path, contents)
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+const HEADER: &str = "# batman recording";
+
+/// Saves every line the player enters so a session can be replayed
+pub struct Recorder {
+    out: BufWriter<File>,
+}
+
+/// Input lines from a recording, fed back to the game one at a time
+pub struct Replay {
+    lines: VecDeque<String>,
+    meta: Vec<(String, String)>,
+}
+
+impl Recorder {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(out, "{HEADER}")?;
+        out.flush()?;
+        Ok(Self { out })
+    }
+
+    /// Stores a `# key: value` line, read back with [`Replay::meta`]
+    pub fn meta(&mut self, key: &str, value: &str) {
+        let _ = writeln!(self.out, "# {key}: {value}").and_then(|_| self.out.flush());
+    }
+
+    /// Appends `line`, flushing right away so a crash loses nothing
+    pub fn record(&mut self, line: &str) {
+        // Escape lines that would otherwise read back as comments
+        let line = if line.starts_with('#') || line.starts_with('\\') {
+            format!("\\{line}")
+        } else {
+            line.to_string()
+        };
+        let _ = writeln!(self.out, "{line}").and_then(|_| self.out.flush());
+    }
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut lines = VecDeque::new();
+        let mut meta = Vec::new();
+        for line in fs::read_to_string(path)?.lines() {
+            if let Some(comment) = line.strip_prefix('#') {
+                if let Some((key, value)) = comment.split_once(':') {
+                    meta.push((key.trim().to_string(), value.trim().to_string()));
+                }
+            } else {
+                lines.push_back(line.strip_prefix('\\').unwrap_or(line).to_string());
+            }
+        }
+        Ok(Self { lines, meta })
+    }
+
+    pub fn meta(&self, key: &str) -> Option<&str> {
+        self.meta
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn next_line(&mut self) -> Option<String> {
+        self.lines.pop_front()
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.lines.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use super::*;
+
+    #[test]
+    fn record_and_load_round_trip() {
+        let path = env::temp_dir().join(format!("batman-replay-test-{}.txt", process::id()));
+        let lines = ["equip coat", "# not a comment", "\\escaped", "", "leave the building"];
+        {
+            let mut recorder = Recorder::create(&path).unwrap();
+            recorder.meta("seed", "42");
+            for line in lines {
+                recorder.record(line);
+            }
+        }
+
+        let mut replay = Replay::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(replay.meta("seed"), Some("42"));
+        assert_eq!(replay.meta("missing"), None);
+        assert_eq!(replay.remaining(), lines.len());
+        for line in lines {
+            assert_eq!(replay.next_line().as_deref(), Some(line));
+        }
+        assert_eq!(replay.next_line(), None);
+    }
+}
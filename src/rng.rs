@@ -0,0 +1,46 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Small seedable random number generator (SplitMix64); good enough for
+/// dice rolls and reproducible from its seed alone
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+/// A seed that differs from run to run
+pub fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Current state; `Rng::new(rng.state())` continues where `rng` is
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform number in `low..=high`
+    pub fn range(&mut self, low: u32, high: u32) -> u32 {
+        let span = u64::from(high - low) + 1;
+        low + (self.next_u64() % span) as u32
+    }
+
+    /// True `percent` percent of the time
+    pub fn chance(&mut self, percent: u32) -> bool {
+        self.range(1, 100) <= percent
+    }
+}
@@ -0,0 +1,188 @@
+use std::mem;
+use std::path::Path;
+
+use crate::game::{Game, ParseMode};
+use crate::replay::{Recorder, Replay};
+use crate::rng::Rng;
+use crate::stage::{Stage, State};
+use crate::ui::{self, print};
+
+const DEFAULT_SAVE: &str = "batman.sav";
+
+/// Everything `undo` needs to take back one action
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub stage: Stage,
+    pub parse_mode: ParseMode,
+    pub state: State,
+    pub rng: Rng,
+}
+
+fn save_path(args: &str) -> &Path {
+    Path::new(if args.is_empty() { DEFAULT_SAVE } else { args })
+}
+
+impl Game {
+    /// Starts the input log a save replays, at the beginning of a run
+    pub fn begin_history(&mut self) {
+        self.history.clear();
+        self.snapshots.clear();
+        self.checkpoint = None;
+        self.history_rng = self.rng.clone();
+    }
+
+    /// Remembers the game as it is before an action, for `undo`
+    pub fn snapshot(&mut self) {
+        self.snapshots.push(Snapshot {
+            stage: self.stage,
+            parse_mode: self.parse_mode,
+            state: self.state.clone(),
+            rng: self.rng.clone(),
+        });
+    }
+
+    /// Goes back to `state`, but keeps count of the commands and hints the
+    /// player really used; hints already paid for stay paid for
+    pub(crate) fn rewind_state(&mut self, state: State) {
+        let penalty = self.state.hint_penalty - state.hint_penalty;
+        self.state = State {
+            score: state.score - penalty,
+            commands: self.state.commands,
+            hints_used: self.state.hints_used,
+            hint_penalty: self.state.hint_penalty,
+            ..state
+        };
+    }
+
+    pub fn undo(&mut self) {
+        let Some(snapshot) = self.snapshots.pop() else {
+            print("There is nothing to undo\n");
+            return;
+        };
+        self.stage = snapshot.stage;
+        self.parse_mode = snapshot.parse_mode;
+        self.rewind_state(snapshot.state);
+        self.rng = snapshot.rng;
+        print("You rewind time by one step...\n");
+        self.transition(self.stage);
+    }
+
+    /// Saves the run as its seed, the RNG state it started with and every
+    /// line entered since, which replay into exactly the same game
+    pub fn save(&self, args: &str) {
+        let path = save_path(args);
+        let mut recorder = match Recorder::create(path) {
+            Ok(recorder) => recorder,
+            Err(e) => {
+                print(&format!("Could not save to '{}': {e}\n", path.display()));
+                return;
+            }
+        };
+        recorder.meta("seed", &self.seed.to_string());
+        recorder.meta("rng", &self.history_rng.state().to_string());
+        if let Some(difficulty) = self.difficulty {
+            recorder.meta("difficulty", &difficulty.to_string());
+        }
+        recorder.meta("stage", &format!("{:?}", self.stage));
+        for line in &self.history {
+            recorder.record(line);
+        }
+        print(&format!("Saved to '{}'\n", path.display()));
+    }
+
+    pub fn load(&mut self, args: &str) {
+        let path = save_path(args);
+        let mut replay = match Replay::load(path) {
+            Ok(replay) => replay,
+            Err(e) => {
+                print(&format!("Could not load '{}': {e}\n", path.display()));
+                return;
+            }
+        };
+        let seed = replay.meta("seed").and_then(|seed| seed.parse().ok());
+        let rng = replay.meta("rng").and_then(|rng| rng.parse().ok());
+        let (Some(seed), Some(rng)) = (seed, rng) else {
+            print(&format!("'{}' is not a saved game\n", path.display()));
+            return;
+        };
+
+        let mut game = Game::with_seed(seed);
+        game.rng = Rng::new(rng);
+        game.difficulty = replay.meta("difficulty").and_then(|d| d.parse().ok());
+        game.profile = mem::take(&mut self.profile);
+        game.verbose = self.verbose;
+
+        ui::set_muted(true);
+        game.transition(Stage::First);
+        while let Some(line) = replay.next_line() {
+            game.eval(&line);
+        }
+        ui::set_muted(false);
+
+        let expected = replay.meta("stage").map(str::to_string);
+        *self = game;
+        if expected != Some(format!("{:?}", self.stage)) {
+            print("This save did not load cleanly; it may be from another version\n");
+        }
+        print(&format!("Loaded '{}'\n", path.display()));
+        self.transition(self.stage);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    use super::*;
+    use crate::game::Equipment;
+
+    fn play(game: &mut Game, lines: &[&str]) {
+        for line in lines {
+            game.eval(line);
+        }
+    }
+
+    #[test]
+    fn undo_keeps_commands_and_hints() {
+        let mut game = Game::with_seed(7);
+        game.transition(Stage::First);
+        play(&mut game, &["Bob", "1", "1", "yes", "2", "equip the coat", "hint"]);
+        let commands = game.state.commands;
+        game.eval("undo");
+
+        assert!(!game.state.equipment.contains(&Equipment::Coat));
+        assert_eq!(game.state.commands, commands + 1);
+        assert_eq!(game.state.hints_used, 1);
+        assert_eq!(game.state.hint_penalty, 2);
+        assert_eq!(game.state.score, -2);
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let path = env::temp_dir().join(format!("batman-save-test-{}.sav", process::id()));
+        let path = path.to_str().unwrap();
+
+        let mut game = Game::with_seed(7);
+        game.transition(Stage::First);
+        // A name that is also a command must still be replayed as a name
+        play(&mut game, &["Time", "1", "2", "yes", "2", "equip the coat", "score", "leave the building"]);
+        game.save(path);
+
+        let mut loaded = Game::with_seed(1);
+        loaded.load(path);
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(loaded.stage, Stage::OutsideLibrary);
+        assert_eq!(loaded.stage, game.stage);
+        assert_eq!(loaded.parse_mode, game.parse_mode);
+        assert_eq!(loaded.state.name, "Time");
+        assert_eq!(loaded.state.background, game.state.background);
+        assert_eq!(loaded.state.equipment, game.state.equipment);
+        assert_eq!(loaded.state.clock, game.state.clock);
+        assert_eq!(loaded.state.health, game.state.health);
+        assert_eq!(loaded.history, game.history);
+        assert_eq!(loaded.rng.state(), game.rng.state());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::clock::START_TIME;
+use crate::game::Game;
+use crate::stage::Stage;
+use crate::ui::print;
+
+/// Points for every minute to spare when the run ends well
+const POINTS_PER_MINUTE_LEFT: i32 = 2;
+/// Points for every point of health left when the run ends well
+const POINTS_PER_HEALTH: i32 = 10;
+/// Points lost for a nudge, a stronger hint and a solution
+pub const HINT_PENALTIES: [i32; 3] = [2, 5, 10];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Discovery {
+    BathroomMirror,
+    BackDoor,
+    StopDropAndRoll,
+    SlayDragon,
+}
+
+impl Discovery {
+    pub fn points(&self) -> i32 {
+        match self {
+            Discovery::BathroomMirror => 25,
+            Discovery::BackDoor => 20,
+            Discovery::StopDropAndRoll => 15,
+            Discovery::SlayDragon => 100,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Discovery::BathroomMirror => "Admired yourself in the bathroom mirror",
+            Discovery::BackDoor => "Escaped the burning bus",
+            Discovery::StopDropAndRoll => "Put yourself out in the snow",
+            Discovery::SlayDragon => "Slew the dragon",
+        }
+    }
+}
+
+impl Game {
+    /// Scores a discovery the first time it is made
+    pub fn discover(&mut self, discovery: Discovery) {
+        if !self.state.discoveries.contains(&discovery) {
+            self.state.discoveries.push(discovery);
+            self.state.score += discovery.points();
+        }
+    }
+
+    /// Adds the bonus for finishing the run with time and health to spare
+    pub fn award_finish_bonus(&mut self) {
+        self.state.score += self.state.time_left().max(0) * POINTS_PER_MINUTE_LEFT;
+        self.state.score += self.state.health.max(0) * POINTS_PER_HEALTH;
+    }
+
+    pub fn print_score(&self) {
+        print(&format!("Score: {}\n", self.state.score));
+        if self.state.hint_penalty > 0 {
+            print(&format!("  -{:<4} Hints\n", self.state.hint_penalty));
+        }
+        for discovery in &self.state.discoveries {
+            print(&format!("  +{:<4} {}\n", discovery.points(), discovery.description()));
+        }
+    }
+
+    pub fn print_summary(&self) {
+        let state = &self.state;
+        let path: Vec<&str> = state.path.iter().map(Stage::title).collect();
+
+        print("\n*Summary*\n");
+        print(&format!("Score:         {}\n", state.score));
+        print(&format!("Difficulty:    {}\n", state.difficulty));
+        print(&format!(
+            "Time used:     {} minutes\n",
+            START_TIME.minutes_until(state.clock)
+        ));
+        print(&format!("Damage taken:  {}\n", state.damage_taken));
+        print(&format!("Commands:      {}\n", state.commands));
+        print(&format!(
+            "Hints used:    {} (-{} points)\n",
+            state.hints_used, state.hint_penalty
+        ));
+        print(&format!("Path:          {}\n", path.join(" -> ")));
+        for discovery in &state.discoveries {
+            print(&format!("  +{:<4} {}\n", discovery.points(), discovery.description()));
+        }
+        print("\n");
+    }
+}
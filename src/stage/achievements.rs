@@ -0,0 +1,58 @@
+use crate::achievement::{Achievement, Trigger};
+use crate::ending::Ending;
+use crate::game::Equipment;
+use crate::score::Discovery;
+use crate::status::StatusKind;
+
+use super::Stage;
+
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        id: "dragonslayer",
+        name: "Dragonslayer",
+        description: "Complete the demo",
+        trigger: Trigger::Stage(Stage::Finish),
+    },
+    Achievement {
+        id: "popsicle",
+        name: "Popsicle",
+        description: "Freeze to death without a coat",
+        trigger: Trigger::State(|state| {
+            state.ending == Some(Ending::Frozen) && !state.equipment.contains(&Equipment::Coat)
+        }),
+    },
+    Achievement {
+        id: "vanity",
+        name: "Vanity",
+        description: "Admire yourself in the library bathroom",
+        trigger: Trigger::State(|state| state.discoveries.contains(&Discovery::BathroomMirror)),
+    },
+    Achievement {
+        id: "stop-drop-roll",
+        name: "Stop, Drop and Roll",
+        description: "Put yourself out in the snow",
+        trigger: Trigger::State(|state| state.discoveries.contains(&Discovery::StopDropAndRoll)),
+    },
+    Achievement {
+        id: "soaked-and-frozen",
+        name: "Michigan Winter",
+        description: "Be soaked and frozen at the same time",
+        trigger: Trigger::State(|state| {
+            state.has_status(StatusKind::Soaked) && state.has_status(StatusKind::Frozen)
+        }),
+    },
+    Achievement {
+        id: "tardy",
+        name: "Tardy",
+        description: "Miss the start of class",
+        trigger: Trigger::Stage(Stage::LateForClass),
+    },
+    Achievement {
+        id: "early-bird",
+        name: "Early Bird",
+        description: "Slay the dragon with half an hour to spare",
+        trigger: Trigger::State(|state| {
+            state.discoveries.contains(&Discovery::SlayDragon) && state.time_left() >= 30
+        }),
+    },
+];
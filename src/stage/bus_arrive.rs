@@ -1,32 +1,37 @@
 use crate::game::{msg, oops};
-use crate::{game::Game, parser::GameExpr};
+use crate::{game::{Game, GameErr, Result}, parser::GameExpr};
 use crate::lexer::VerbKind;
 use crate::lexer::NounKind;
-use crate::lexer::AdjKind;
-use crate::lexer::AdverbKind;
+use crate::clock::Event;
 use super::Stage;
 
 impl Game {
-    pub fn eval_bus_arrive(&mut self, game: GameExpr) -> Stage {
-        let (verb, noun, adverb, adj) = match game {
-            GameExpr::Svn { verb, noun, adverb, adj } => (verb, noun, adverb, adj),
-            _ => unreachable!(),
+    pub fn eval_bus_arrive(&mut self, game: GameExpr) -> Result<Stage> {
+        let (verb, noun) = match game {
+            GameExpr::Svn { verb, noun, .. } => (verb, noun),
+            _ => return Err(GameErr::UnexpectedInput),
         };
 
-        match (verb, noun) {
+        Ok(match (verb, noun) {
             (VerbKind::Do, NounKind::Nothing) | (VerbKind::Wait, NounKind::Bus) => {
+                if self.state.bus_at_stop {
+                    self.wait_for(Event::BusDeparts);
+                }
                 msg("You stand in front of the bus like an idiot\nand it takes off without you");
-                self.adjust_time(-4);
                 Stage::TransitOnFoot
             }
-            (VerbKind::Enter, NounKind::Bus) => {
+            (VerbKind::Enter | VerbKind::Board, NounKind::Bus) if self.state.bus_at_stop => {
                 Stage::BusFire
             }
+            (VerbKind::Enter | VerbKind::Board, NounKind::Bus) => {
+                msg("The bus pulls away just as you reach the door...\n");
+                Stage::TransitOnFoot
+            }
             _ => {
                 oops();
                 self.stage
             }
-        }
+        })
     }
 }
 
@@ -1,28 +1,45 @@
 use crate::game::{msg, oops};
-use crate::{game::Game, parser::GameExpr};
+use crate::score::Discovery;
+use crate::status::StatusKind;
+use crate::{game::{Game, GameErr, Result}, parser::GameExpr};
 use crate::lexer::VerbKind;
 use crate::lexer::NounKind;
-use crate::lexer::AdjKind;
-use crate::lexer::AdverbKind;
-use super::Stage;
+use super::{Stage, BUS_FIRE_BURN_TURNS};
+
+/// Percent chance per turn that the fire spreads to the player
+const FIRE_SPREAD_CHANCE: u32 = 60;
 
 impl Game {
-    pub fn eval_bus_fire(&mut self, game: GameExpr) -> Stage {
-        let (verb, noun, adverb, adj) = match game {
-            GameExpr::Svn { verb, noun, adverb, adj } => (verb, noun, adverb, adj),
-            _ => unreachable!(),
+    pub fn eval_bus_fire(&mut self, game: GameExpr) -> Result<Stage> {
+        let (verb, noun) = match game {
+            GameExpr::Svn { verb, noun, .. } => (verb, noun),
+            _ => return Err(GameErr::UnexpectedInput),
         };
 
-        match (verb, noun) {
+        Ok(match (verb, noun) {
             (VerbKind::Run, NounKind::Away) => {
                 msg("You try to exit the front of the bus, but the entrance is blocked!");
+                self.spread_fire();
                 self.stage
             }
+            (VerbKind::Exit | VerbKind::Leave | VerbKind::Run, NounKind::Back) => {
+                msg("You kick open the back door and tumble out into the snow...\n");
+                self.discover(Discovery::BackDoor);
+                Stage::TransitOnFoot
+            }
             _ => {
                 oops();
+                self.spread_fire();
                 self.stage
             }
+        })
+    }
+
+    /// Every turn spent on the bus, the flames may catch you again
+    fn spread_fire(&mut self) {
+        if self.rng.chance(FIRE_SPREAD_CHANCE) {
+            msg("The {red}flames{/} spread to your clothes!\n");
+            self.inflict(StatusKind::Burning, BUS_FIRE_BURN_TURNS);
         }
     }
 }
-
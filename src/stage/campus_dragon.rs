@@ -1,19 +1,17 @@
 use crate::game::{msg, oops};
-use crate::{game::Game, parser::GameExpr};
+use crate::{game::{Game, GameErr, Result}, parser::GameExpr};
 use crate::lexer::VerbKind;
 use crate::lexer::NounKind;
-use crate::lexer::AdjKind;
-use crate::lexer::AdverbKind;
 use super::Stage;
 
 impl Game {
-    pub fn eval_campus_dragon(&mut self, game: GameExpr) -> Stage {
-        let (verb, noun, adverb, adj) = match game {
-            GameExpr::Svn { verb, noun, adverb, adj } => (verb, noun, adverb, adj),
-            _ => unreachable!(),
+    pub fn eval_campus_dragon(&mut self, game: GameExpr) -> Result<Stage> {
+        let (verb, noun) = match game {
+            GameExpr::Svn { verb, noun, .. } => (verb, noun),
+            _ => return Err(GameErr::UnexpectedInput),
         };
 
-        match (verb, noun) {
+        Ok(match (verb, noun) {
             (VerbKind::Run, NounKind::Away) => {
                 msg("Your pride refuses to let you run away...\n");
                 self.stage
@@ -25,7 +23,7 @@ impl Game {
                 oops();
                 self.stage
             }
-        }
+        })
     }
 }
 
@@ -1,35 +1,67 @@
-use crate::{game::Game, parser::GameExpr};
-use crate::game::Result;
-use ncurses::*;
+use crate::{game::{Game, GameErr, Result}, parser::GameExpr};
+use crate::character::{validate_name, Background, Pronouns};
+use crate::difficulty::Difficulty;
+use crate::ending::Ending;
 
 use super::{Stage, State};
 
 impl Game {
-    pub fn eval_first(&mut self, game: GameExpr) -> Stage {
+    pub fn eval_first(&mut self, game: GameExpr) -> Result<Stage> {
+        let GameExpr::Raw(name) = game else {
+            return Err(GameErr::UnexpectedInput);
+        };
+        let name = validate_name(&name)?;
+
         self.state = State::default();
-        match game {
-            GameExpr::Raw(name) => {
-                self.state.name = name.to_string();
-            },
-            _ => {
-                self.state.name = "Jeff".to_string();
-            },
-        }
+        self.delay_buses();
+        self.state.name = name;
+        self.narrate("Greetings, *{name}*\n");
 
-        addstr(&format!("Greetings, {}\n", self.state.name));
+        Ok(Stage::ChoosePronouns)
+    }
 
-        Stage::PlayConfirm
+    pub fn eval_choose_pronouns(&mut self, game: GameExpr) -> Result<Stage> {
+        let GameExpr::Choice(choice) = game else {
+            return Err(GameErr::UnexpectedInput);
+        };
+        self.state.pronouns = Pronouns::from_id(choice).ok_or(GameErr::UnexpectedInput)?;
+        Ok(Stage::ChooseBackground)
     }
 
-    pub fn eval_playconfirm(&mut self, game: GameExpr) -> Stage {
-        match game {
-            GameExpr::Confirm(true) => {
-                Stage::Library
-            }
+    pub fn eval_choose_background(&mut self, game: GameExpr) -> Result<Stage> {
+        let GameExpr::Choice(choice) = game else {
+            return Err(GameErr::UnexpectedInput);
+        };
+        let background = Background::from_id(choice).ok_or(GameErr::UnexpectedInput)?;
+        self.set_background(background);
+        Ok(Stage::PlayConfirm)
+    }
+
+    pub fn eval_playconfirm(&mut self, game: GameExpr) -> Result<Stage> {
+        Ok(match game {
+            GameExpr::Confirm(true) => match self.difficulty {
+                Some(difficulty) => {
+                    self.set_difficulty(difficulty);
+                    Stage::Library
+                }
+                None => Stage::ChooseDifficulty,
+            },
             GameExpr::Confirm(false) => {
+                self.reach_ending(Ending::Declined);
                 Stage::Quit
             }
-            _ => unreachable!()
-        }
+            _ => return Err(GameErr::UnexpectedInput),
+        })
+    }
+
+    pub fn eval_choose_difficulty(&mut self, game: GameExpr) -> Result<Stage> {
+        let GameExpr::Choice(choice) = game else {
+            return Err(GameErr::UnexpectedInput);
+        };
+        let difficulty = choice
+            .parse::<Difficulty>()
+            .map_err(|()| GameErr::UnexpectedInput)?;
+        self.set_difficulty(difficulty);
+        Ok(Stage::Library)
     }
 }
@@ -0,0 +1,228 @@
+use crate::game::{Equipment, Game};
+use crate::score::HINT_PENALTIES;
+use crate::ui::print;
+
+use super::Stage;
+
+fn possible_nouns(nouns: &[&str]) {
+    if nouns.is_empty() {
+        return;
+    }
+    print("Here are some other things around you...\n");
+    for noun in nouns {
+        print(&format!("[{noun}]\n"));
+    }
+}
+
+impl Game {
+    /// Hints for where the player is, from a gentle nudge to the solution,
+    /// and the things around them
+    fn hints(&self) -> (Vec<&'static str>, Vec<&'static str>) {
+        let state = &self.state;
+        let has_coat = state.equipment.contains(&Equipment::Coat);
+        let has_sword = state.equipment.contains(&Equipment::Sword);
+        let walk_kills = self.walk_kills();
+        // A retry or restart forgets everything before it, so undo may not
+        // reach the library any more
+        let can_undo = self.snapshots.iter().any(|snapshot| snapshot.stage == Stage::Library);
+        match self.stage {
+            Stage::First => (vec!["Just type your name..."], vec![]),
+            Stage::PlayConfirm | Stage::Finish => (vec!["Try: 'yes' or 'no'"], vec![]),
+            Stage::ChoosePronouns | Stage::ChooseBackground => {
+                (vec!["Pick an option by its number or name"], vec![])
+            }
+            Stage::ChooseDifficulty => (
+                vec!["Easy gives you more time and less damage; hard gives you less"],
+                vec![],
+            ),
+            Stage::GameOver | Stage::LateForClass => {
+                (vec!["Pick an option by its number or name"], vec![])
+            }
+            Stage::Quit | Stage::TransitOnFoot => (vec![], vec![]),
+            Stage::Library if !has_coat => (
+                vec![
+                    "It's the middle of January; are you dressed for the walk?",
+                    "Your [coat] is hanging by the door...",
+                    "Type 'equip coat', then 'leave the building'",
+                ],
+                vec!["coat", "building", "sword", "bathroom"],
+            ),
+            Stage::Library if !has_sword => (
+                vec![
+                    "You're ready for the weather, but are you ready for anything else?",
+                    "Legends speak of a [sword] hidden in this library...",
+                    "Type 'equip sword', then 'leave the building'",
+                ],
+                vec!["building", "sword", "bathroom"],
+            ),
+            Stage::Library => (
+                vec![
+                    "You have everything you need; class won't wait",
+                    "Head out of the [building]",
+                    "Type 'leave the building'",
+                ],
+                vec!["building", "bathroom"],
+            ),
+            Stage::OutsideLibrary if walk_kills => (
+                vec![
+                    "Around you is a sea of white, and you're shivering already...",
+                    "Bus or no bus, you won't make it to campus without your [coat]",
+                    if can_undo {
+                        "Type 'undo' to go back into the library, then 'equip coat'"
+                    } else {
+                        "Type 'restart', and 'equip coat' before you leave the library"
+                    },
+                ],
+                vec!["snow", "library", "campus", "bus"],
+            ),
+            Stage::OutsideLibrary => (
+                vec![
+                    "Around you is a sea of white, but your bus is coming soon...",
+                    "You can either wait for the [bus] or continue on foot to [campus]",
+                    if has_coat {
+                        "Your coat will keep you warm; type 'go to campus'"
+                    } else {
+                        "It's cold, but you'll make it; type 'go to campus'"
+                    },
+                ],
+                vec!["snow", "library", "campus", "bus"],
+            ),
+            Stage::BusArrive if walk_kills => (
+                vec![
+                    "Whichever way you go, you'll end up walking through the cold",
+                    "Your [coat] is still hanging in the library...",
+                    if can_undo {
+                        "Type 'undo' until you're back in the library, then 'equip coat'"
+                    } else {
+                        "Type 'restart', and 'equip coat' before you leave the library"
+                    },
+                ],
+                vec!["bus"],
+            ),
+            Stage::BusArrive if state.bus_at_stop => (
+                vec![
+                    "The doors of the bus hiss open...",
+                    "Riding beats walking; get on the [bus]",
+                    "Type 'board the bus', and mind the back door",
+                ],
+                vec!["bus"],
+            ),
+            Stage::BusArrive => (
+                vec![
+                    "The bus is gone; campus isn't getting any closer",
+                    "There's nothing left to wait for",
+                    "Type 'do nothing' to set off on foot",
+                ],
+                vec![],
+            ),
+            Stage::BusFire if walk_kills => (
+                vec![
+                    "The back door leads out into the snow, and you have no [coat]",
+                    "Your coat is still hanging in the library...",
+                    if can_undo {
+                        "Type 'undo' until you're back in the library, then 'equip coat'"
+                    } else {
+                        "Type 'restart', and 'equip coat' before you leave the library"
+                    },
+                ],
+                vec!["extinguisher", "back"],
+            ),
+            Stage::BusFire => (
+                vec![
+                    "The front is blocked, but maybe there is a way out the [back]...",
+                    "Get off this bus through the [back]",
+                    "Type 'exit the back', then 'roll in the snow' if you're burning",
+                ],
+                vec!["extinguisher", "back"],
+            ),
+            Stage::CampusDragon if has_sword => (
+                vec![
+                    "Running isn't an option; face the [dragon]",
+                    "Attack the [dragon]",
+                    "Type 'strike the dragon', then strike it with your sword",
+                ],
+                vec!["fist", "pen", "dragon", "sword"],
+            ),
+            Stage::CampusDragon => (
+                vec![
+                    "Running isn't an option; face the [dragon]",
+                    "You'll want something sharper than a pen for this...",
+                    if can_undo {
+                        "The sword is back in the library; type 'undo' to go back for it"
+                    } else {
+                        "The sword is back in the library; type 'restart' to go back for it"
+                    },
+                ],
+                vec!["fist", "pen", "dragon"],
+            ),
+            Stage::StrikeDragon if has_sword => (
+                vec![
+                    "Pick your sharpest option",
+                    "Your [sword] was made for this",
+                    "Type 'strike sword'",
+                ],
+                vec!["fist", "pen", "sword"],
+            ),
+            Stage::StrikeDragon => (
+                vec![
+                    "Your fists won't do much against a dragon",
+                    "You left the sword back in the library...",
+                    if can_undo {
+                        "Type 'undo' until you're back in the library, or 'restart'"
+                    } else {
+                        "Type 'restart', and 'equip sword' before you leave the library"
+                    },
+                ],
+                vec!["fist", "pen"],
+            ),
+        }
+    }
+
+    /// Gives the next hint for the current situation; every hint about the
+    /// same thing is more explicit than the last, and costs more points
+    /// unless the difficulty makes hints free
+    pub fn print_hint(&mut self) {
+        self.print_status_effects();
+        let (tiers, nouns) = self.hints();
+        // Start over with a nudge whenever the situation changes
+        let topic = tiers.first().copied();
+        if self.state.hint_topic != topic {
+            self.state.hint_topic = topic;
+            self.state.hint_level = 0;
+        }
+        if let Some(last) = tiers.len().checked_sub(1) {
+            let level = self.state.hint_level.min(last);
+            self.narrate(&format!("{}\n", tiers[level]));
+            if self.stage.is_on_the_clock() {
+                if self.state.difficulty.charges_hints() {
+                    let penalty = HINT_PENALTIES[level.min(HINT_PENALTIES.len() - 1)];
+                    self.state.score -= penalty;
+                    self.state.hint_penalty += penalty;
+                }
+                self.state.hint_level += 1;
+            }
+        }
+        possible_nouns(&nouns);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_to_restart_once_undo_cannot_reach_the_library() {
+        let mut game = Game::with_seed(7);
+        game.transition(Stage::First);
+        for line in ["Bob", "1", "1", "yes", "2", "leave the building"] {
+            game.eval(line);
+        }
+        let (tiers, _) = game.hints();
+        assert!(tiers[2].contains("'undo'"));
+
+        game.snapshots.clear();
+        let (tiers, _) = game.hints();
+        assert!(tiers[2].contains("'restart'"));
+        assert!(!tiers[2].contains("'undo'"));
+    }
+}
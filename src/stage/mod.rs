@@ -1,9 +1,21 @@
 use std::collections::HashSet;
 
+use crate::character::{Background, Pronouns};
+use crate::choice::Choice;
+use crate::clock::{bus_timetable, Clock, Scheduled, CLASS_TIME, START_TIME};
+use crate::damage::DamageKind;
+use crate::difficulty::Difficulty;
+use crate::ending::Ending;
 use crate::game::{msg, Equipment, Game, ParseMode};
-use ncurses::*;
+use crate::markup;
+use crate::score::Discovery;
+use crate::status::{StatusEffect, StatusKind};
+use crate::template::render;
+use crate::ui::{self, print};
 
+pub mod achievements;
 pub mod first;
+pub mod hints;
 pub mod library;
 pub mod outside_library;
 pub mod campus_dragon;
@@ -11,11 +23,14 @@ pub mod bus_fire;
 pub mod bus_arrive;
 pub mod strike_dragon;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Stage {
     First,
     Finish,
+    ChoosePronouns,
+    ChooseBackground,
     PlayConfirm,
+    ChooseDifficulty,
     Library,
     OutsideLibrary,
     TransitOnFoot,
@@ -24,178 +39,386 @@ pub enum Stage {
     CampusDragon,
     BusFire,
     StrikeDragon,
+    LateForClass,
     Quit,
 }
 
-fn print_confirm_hint() {
-    addstr("Try: 'yes' or 'no'\n");
-}
+impl Stage {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Stage::First => "Welcome",
+            Stage::Finish => "Finish",
+            Stage::PlayConfirm => "Play?",
+            Stage::ChoosePronouns => "Pronouns",
+            Stage::ChooseBackground => "Background",
+            Stage::ChooseDifficulty => "Difficulty",
+            Stage::Library => "Library",
+            Stage::OutsideLibrary => "Outside the library",
+            Stage::TransitOnFoot => "On foot",
+            Stage::BusArrive => "Bus stop",
+            Stage::GameOver => "Game over",
+            Stage::CampusDragon => "Campus",
+            Stage::BusFire => "Burning bus",
+            Stage::StrikeDragon => "Dragon fight",
+            Stage::LateForClass => "Late for class",
+            Stage::Quit => "Quit",
+        }
+    }
 
-pub fn possible_nouns(nouns: &[&str]) {
-    addstr("Here are some other things around you...\n");
-    for noun in nouns {
-        addstr(noun);
-        addstr("\n");
+    /// Whether the class deadline applies while in this stage
+    pub fn is_on_the_clock(&self) -> bool {
+        !matches!(
+            self,
+            Stage::First
+                | Stage::PlayConfirm
+                | Stage::ChoosePronouns
+                | Stage::ChooseBackground
+                | Stage::ChooseDifficulty
+                | Stage::Finish
+                | Stage::GameOver
+                | Stage::LateForClass
+                | Stage::Quit
+        )
     }
 }
 
+/// Cold damage taken walking from the library to campus
+pub const WALK_COLD_DAMAGE: u32 = 12;
+
+/// Turns the flames keep burning after the last turn spent on the bus
+pub(crate) const BUS_FIRE_BURN_TURNS: u32 = 4;
+
 #[derive(Debug, Clone)]
 pub struct State {
     pub name: String,
-    pub time_left: i32,
+    pub pronouns: Pronouns,
+    pub background: Background,
+    pub clock: Clock,
+    pub class_time: Clock,
+    pub schedule: Vec<Scheduled>,
+    pub bus_at_stop: bool,
+    /// When the last bus pulled up at the stop
+    pub bus_arrived_at: Option<Clock>,
     pub health: i32,
-    pub armor: u32,
     pub equipment: HashSet<Equipment>,
+    pub status: Vec<StatusEffect>,
+    pub score: i32,
+    pub discoveries: Vec<Discovery>,
+    pub damage_taken: u32,
+    pub commands: u32,
+    /// Story stages visited this run, in order
+    pub path: Vec<Stage>,
+    /// How the run ended, once it has
+    pub ending: Option<Ending>,
+    pub difficulty: Difficulty,
+    pub hints_used: u32,
+    /// First hint of the situation hints were last given for, and how many
+    /// have been given since
+    pub hint_topic: Option<&'static str>,
+    pub hint_level: usize,
+    /// Points lost to hints
+    pub hint_penalty: i32,
+}
+
+impl State {
+    pub fn equipment_list(&self) -> String {
+        if self.equipment.is_empty() {
+            return "nothing equipt".to_string();
+        }
+        let mut items: Vec<String> = self
+            .equipment
+            .iter()
+            .map(|item| format!("{item:?}").to_lowercase())
+            .collect();
+        items.sort();
+        items.join(", ")
+    }
 }
 
 impl Default for State {
     fn default() -> Self {
         Self {
             name: "Jeff".to_string(),
-            time_left: 60,
+            pronouns: Pronouns::default(),
+            background: Background::default(),
+            clock: START_TIME,
+            class_time: CLASS_TIME,
+            schedule: bus_timetable(),
+            bus_at_stop: false,
+            bus_arrived_at: None,
             health: 10,
-            armor: 0,
             equipment: HashSet::new(),
+            status: Vec::new(),
+            score: 0,
+            discoveries: Vec::new(),
+            damage_taken: 0,
+            commands: 0,
+            path: Vec::new(),
+            ending: None,
+            difficulty: Difficulty::default(),
+            hints_used: 0,
+            hint_topic: None,
+            hint_level: 0,
+            hint_penalty: 0,
         }
     }
 }
 
 impl Game {
+    /// Whether the walk to campus, which every route ends in, would be fatal
+    pub fn walk_kills(&self) -> bool {
+        self.damage_for(DamageKind::Cold, WALK_COLD_DAMAGE) as i32 >= self.state.health
+    }
+
     pub fn print_time_left(&mut self) {
-        msg(&format!("You have {} minutes left...\n", self.state.time_left));
+        let mut s = render("It is {clock}; you have {time_left|minute|minutes} left...\n", &self.state);
+        for effect in &self.state.status {
+            s.push_str(&format!("You are {}\n", effect.kind.name()));
+        }
+        msg(&s);
     }
 
-    pub fn adjust_time(&mut self, delta: i32) {
-        self.state.time_left += delta;
-        self.print_time_left();
+    pub fn print_stats(&self) {
+        let state = &self.state;
+        let mut s = format!(
+            "Name:      {} ({}/{}), {}\n",
+            markup::escape(&state.name),
+            state.pronouns.subject(),
+            state.pronouns.object(),
+            state.background.name()
+        );
+        s.push_str(&format!("Health:    {}\n", state.health));
+        s.push_str(&format!(
+            "Armor:     {}% physical, {}% fire, {}% cold\n",
+            state.resistance(DamageKind::Physical),
+            state.resistance(DamageKind::Fire),
+            state.resistance(DamageKind::Cold)
+        ));
+        s.push_str(&format!(
+            "Time:      {} ({} minutes left)\n",
+            state.clock,
+            state.time_left()
+        ));
+        s.push_str(&format!("Equipment: {}\n", state.equipment_list()));
+        s.push_str(&format!("Seed:      {}\n", self.seed));
+        s.push_str(&format!("Level:     {}\n", state.difficulty));
+        for effect in &state.status {
+            s.push_str(&format!(
+                "Status:    {} ({} turns left)\n",
+                effect.kind.name(),
+                effect.turns_left
+            ));
+        }
+        msg(&s);
     }
 
-    pub fn transition(&mut self, mut stage: Stage) {
+    /// Refreshes the status line and the sidebar pane
+    pub fn update_status(&self) {
+        let state = &self.state;
+        let mut line = format!(
+            " {} | HP {} | {} ({} min left) | {}",
+            state.name,
+            state.health,
+            state.clock,
+            state.time_left(),
+            state.equipment_list()
+        );
+        for effect in &state.status {
+            line.push_str(&format!(" | {}", effect.kind.name()));
+        }
+
+        let mut sidebar = vec![
+            state.name.clone(),
+            String::new(),
+            format!("Health  {}", state.health),
+            format!("Time    {}", state.clock),
+            format!("Left    {} min", state.time_left()),
+            String::new(),
+            "Armor".to_string(),
+            format!("  physical {}%", state.resistance(DamageKind::Physical)),
+            format!("  fire     {}%", state.resistance(DamageKind::Fire)),
+            format!("  cold     {}%", state.resistance(DamageKind::Cold)),
+            String::new(),
+            "Equipment".to_string(),
+        ];
+        sidebar.extend(state.equipment_list().split(", ").map(|item| format!("  {item}")));
+        if !state.status.is_empty() {
+            sidebar.push(String::new());
+            sidebar.push("Status".to_string());
+            for effect in &state.status {
+                sidebar.push(format!("  {} ({})", effect.kind.name(), effect.turns_left));
+            }
+        }
+        ui::set_status(&line, sidebar);
+    }
+
+    /// Redirects to the late ending once time has run out
+    fn check_clock(&self, stage: Stage) -> Stage {
+        if stage.is_on_the_clock() && self.state.time_left() <= 0 {
+            Stage::LateForClass
+        } else {
+            stage
+        }
+    }
+
+    pub fn transition(&mut self, stage: Stage) {
+        let mut stage = self.check_clock(stage);
+        if !self.verbose && stage == self.stage && stage.is_on_the_clock() {
+            ui::separator();
+            print(&format!("*{}* ({} minutes left)\n", stage.title(), self.state.time_left()));
+            return;
+        }
         self.parse_mode = self.transition_aux(&mut stage);
         self.stage = stage;
     }
 
     pub fn transition_aux(&mut self, stage: &mut Stage) -> ParseMode {
-        clear();
-        match stage {
+        ui::separator();
+        if stage.is_on_the_clock() && self.state.path.last() != Some(stage) {
+            self.state.path.push(*stage);
+        }
+        let parse_mode = match stage {
             Stage::First => {
-                addstr("Welcome! What is your name\n");
-                self.name = "Jeff".to_string();
+                self.begin_history();
+                print("Welcome! What is your name\n");
                 ParseMode::Raw
             }
+            Stage::ChoosePronouns => {
+                print("Which pronouns should the story use for you?\n");
+                self.offer(Pronouns::CHOICES.to_vec())
+            }
+            Stage::ChooseBackground => {
+                print("What were you before this fateful morning?\n");
+                self.offer(Background::CHOICES.to_vec())
+            }
             Stage::PlayConfirm => {
-                addstr("Would you like to play the game?\n");
+                print("Would you like to play the game?\n");
                 ParseMode::Confirm
             }
+            Stage::ChooseDifficulty => {
+                print("Choose a difficulty:\n");
+                self.offer(vec![
+                    Choice::new("easy", "Easy - more time, less damage, free hints", &["e"]),
+                    Choice::new("normal", "Normal", &["n"]),
+                    Choice::new("hard", "Hard - less time, fewer hints", &["h"]),
+                ])
+            }
             Stage::Library => {
-                addstr("The setting is Houghton, mid January...\n");
-                addstr("Your class begins in an hour...\n");
-                addstr("You are in the Library Resturant and would like to go back to campus...\n");
-                addstr("What should you do?\n");
+                print("The setting is Houghton, mid January...\n");
+                self.print_clock();
+                self.narrate(concat!(
+                    "{if before 8:30}The sun has barely risen over the Keweenaw...",
+                    "{else}The morning sun glares off the snow outside...{/if}\n",
+                    "Your class begins at {class_time}...\n",
+                    "You are in the Library Resturant and would like to go back to [campus]...\n",
+                    "What should you do?\n",
+                ));
                 ParseMode::Grammar
             }
             Stage::BusFire => {
-                addstr("You board the bus headed to campus...\n");
-                addstr("Everything is going fine until...\n");
-                addstr("The bus becomes engulfed in flames!\n");
+                if matches!(self.stage, Stage::BusFire) {
+                    print("The bus is still engulfed in {red}*flames*{/}!\n");
+                } else {
+                    print("You board the bus headed to campus...\n");
+                    print("Everything is going fine until...\n");
+                    print("The bus becomes engulfed in {red}*flames*{/}!\n");
+                    print("Your sleeve {red}catches fire{/}!\n");
+                    self.inflict(StatusKind::Burning, BUS_FIRE_BURN_TURNS);
+                }
                 ParseMode::Grammar
             }
             Stage::StrikeDragon => {
-                addstr("Strike the dragon with what?\n");
+                print("Strike the {red}*dragon*{/} with what?\n");
                 ParseMode::Grammar
             }
             Stage::Finish => {
-                addstr("Grats' on completing the demo...\n");
-                addstr("Would you like to play again?\n");
+                self.narrate(concat!(
+                    "{green}*Grats' on completing the demo...*{/}\n",
+                    "Word spreads across campus of how {name} slew the dragon;\n",
+                    "they'll be telling {pronoun.possessive} story for years...\n",
+                ));
+                self.reach_ending(Ending::Victory);
+                self.print_summary();
+                print("Would you like to play again?\n");
                 ParseMode::Confirm
             }
             Stage::OutsideLibrary => {
-                addstr("You swing open the door and are hit with a big gust of wind...\n");
-                // TODO if equip coat, say how it's fine
-                addstr("What now?\n");
+                self.narrate(concat!(
+                    "You swing open the door and are hit with a big gust of wind...\n",
+                    "{if has coat}Good thing you're bundled up in your coat...",
+                    "{else}The wind cuts straight through your clothes...{/if}\n",
+                    "{if bus}The bus is idling at the stop across the street...\n{/if}",
+                    "What now?\n",
+                ));
                 ParseMode::Grammar
             }
             Stage::BusArrive => {
-                addstr("After some time, the bus arrives...\n");
-                addstr("A new decision bestows you...\n");
+                self.narrate(concat!(
+                    "{if bus}At {bus_arrival}, the bus arrives...\n",
+                    "{else}The bus has pulled away; the stop is empty...\n{/if}",
+                ));
+                print("A new decision bestows you...\n");
                 ParseMode::Grammar
             }
             Stage::TransitOnFoot => {
-                addstr("You're too good for a bus...\n");
-                if self.state.equipment.contains(&Equipment::Coat) {
-                    addstr("You decide to walk instead...\n");
-                    *stage = Stage::CampusDragon;
-                    self.transition_aux(stage)
+                if matches!(self.stage, Stage::OutsideLibrary) {
+                    print("You're too good for a bus...\n");
+                    print("You decide to walk instead...\n");
                 } else {
-                    addstr("As you tread through the snow, you feel\n");
-                    addstr("your legs weaken as you become enveloped in cold...\n");
-                    msg("You have frozen to death");
+                    print("You set off through the snow towards campus...\n");
+                }
+                let damage = self.do_damage(DamageKind::Cold, WALK_COLD_DAMAGE);
+                if self.state.is_dead() {
+                    print("As you tread through the snow, you feel\n");
+                    print("your legs weaken as you become enveloped in cold...\n");
+                    msg("You have {cyan}*frozen to death*{/}");
                     *stage = Stage::GameOver;
-                    self.transition_aux(stage)
+                } else {
+                    if damage > WALK_COLD_DAMAGE / 2 {
+                        msg("The {cyan}cold{/} bites deep; you can no longer feel your fingers...");
+                        self.inflict(StatusKind::Frozen, 3);
+                    } else {
+                        msg("The cold bites, but you push on through the snow...");
+                    }
+                    *stage = Stage::CampusDragon;
                 }
+                self.transition_aux(stage)
             }
             Stage::CampusDragon => {
-                addstr("You made it to the campus...\n");
-                addstr("However, a dragon blocks your way...\n");
+                print("You made it to the campus...\n");
+                print("However, a {red}*dragon*{/} blocks your way...\n");
                 ParseMode::Grammar
             }
             Stage::GameOver => {
-                addstr("Unfortunately you have game overed...\n");
-                addstr("If you would like to try again, type 'yes'\n");
-                ParseMode::Confirm
+                self.narrate(concat!(
+                    "Unfortunately you have *game overed*...\n",
+                    "The seat saved for {pronoun.object} in class stays empty...\n",
+                ));
+                if let Some(ending) = self.state.ending {
+                    self.reach_ending(ending);
+                }
+                self.print_summary();
+                self.offer(self.retry_choices())
+            }
+            Stage::LateForClass => {
+                self.narrate(concat!(
+                    "By the time you make it to class, the lecture is already over...\n",
+                    "You were *late for class*\n",
+                    "The professor glances at {name} and marks {pronoun.object} absent anyway...\n",
+                ));
+                self.reach_ending(Ending::Late);
+                self.print_summary();
+                self.offer(self.retry_choices())
             }
             Stage::Quit => {
-                addstr("Bye!\n");
+                print("Bye!\n");
                 self.is_running = false;
                 ParseMode::Raw
             }
+        };
+        if stage.is_checkpoint() {
+            self.reach_checkpoint(*stage, parse_mode);
         }
-    }
-
-    pub fn print_hint(&self) {
-        match self.stage {
-            Stage::First => {
-                addstr("Just type your name...\n");
-            }
-            Stage::PlayConfirm => {
-                print_confirm_hint();
-            }
-            Stage::Library => {
-                addstr("Try: 'equip coat' or 'leave the building'\n");
-                possible_nouns(&["coat", "building", "sword", "bathroom"]);
-            }
-            Stage::OutsideLibrary => {
-                addstr("Around you is a sea of white\n");
-                addstr("But knowing your bus is coming soon, you can either\n");
-                addstr("wait, or continue on foot...\n");
-                possible_nouns(&["snow", "library", "campus"]);
-            }
-            Stage::Quit => unreachable!(),
-            Stage::TransitOnFoot => {
-            }
-            Stage::BusArrive => {
-                possible_nouns(&["bus"]);
-            }
-            Stage::GameOver => {
-            }
-            Stage::Finish => (),
-            Stage::BusFire => {
-                possible_nouns(&["extinguisher"]);
-            }
-            Stage::CampusDragon => {
-                let mut nouns = vec!["fist", "pen", "dragon"];
-                if self.state.equipment.contains(&Equipment::Sword) {
-                    nouns.push("sword");
-                }
-                possible_nouns(nouns.as_slice());
-            }
-            Stage::StrikeDragon => {
-                let mut nouns = vec!["fist", "pen"];
-                if self.state.equipment.contains(&Equipment::Sword) {
-                    nouns.push("sword");
-                }
-                possible_nouns(nouns.as_slice());
-            }
-        }
+        parse_mode
     }
 }
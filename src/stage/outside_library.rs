@@ -1,9 +1,8 @@
 use crate::game::{msg, oops};
-use crate::{game::Game, parser::GameExpr};
+use crate::{game::{Game, GameErr, Result}, parser::GameExpr};
 use crate::lexer::VerbKind;
 use crate::lexer::NounKind;
-use crate::lexer::AdjKind;
-use crate::lexer::AdverbKind;
+use crate::clock::Event;
 use super::Stage;
 
 fn go_on_foot(verb: VerbKind, noun: NounKind) -> bool {
@@ -12,17 +11,21 @@ fn go_on_foot(verb: VerbKind, noun: NounKind) -> bool {
 }
 
 impl Game {
-    pub fn eval_outside_library(&mut self, game: GameExpr) -> Stage {
-        let (verb, noun, adverb, adj) = match game {
-            GameExpr::Svn { verb, noun, adverb, adj } => (verb, noun, adverb, adj),
-            _ => unreachable!(),
+    pub fn eval_outside_library(&mut self, game: GameExpr) -> Result<Stage> {
+        let (verb, noun) = match game {
+            GameExpr::Svn { verb, noun, .. } => (verb, noun),
+            _ => return Err(GameErr::UnexpectedInput),
         };
 
-        match (verb, noun) {
+        Ok(match (verb, noun) {
             (VerbKind::Do, NounKind::Nothing) | (VerbKind::Wait, NounKind::Bus) => {
                 msg("You decide to wait for the bus...\n");
-                self.adjust_time(-10);
-                Stage::BusArrive
+                if self.wait_for(Event::BusArrives) {
+                    Stage::BusArrive
+                } else {
+                    msg("No more buses are coming this morning...\n");
+                    self.stage
+                }
             }
             _ if go_on_foot(verb, noun) => {
                 Stage::TransitOnFoot
@@ -31,7 +34,7 @@ impl Game {
                 oops();
                 self.stage
             }
-        }
+        })
     }
 }
 
@@ -1,34 +1,42 @@
+use crate::damage::DamageKind;
 use crate::game::{msg, oops, Equipment};
-use crate::{game::Game, parser::GameExpr};
+use crate::score::Discovery;
+use crate::status::StatusKind;
+use crate::{game::{Game, GameErr, Result}, parser::GameExpr};
 use crate::lexer::VerbKind;
 use crate::lexer::NounKind;
-use crate::lexer::AdjKind;
-use crate::lexer::AdverbKind;
 use super::Stage;
 
-impl Game {
-    pub fn do_damage(&mut self, n: u32) {
-        let damage = n as f32 * ((100 - self.state.armor) as f32 / 100.0);
-        self.state.health -= damage as i32;
-    }
+/// Physical damage dealt by the dragon when you fail to fight back
+const DRAGON_DAMAGE: u32 = 4;
 
+/// Percent chance that the dragon's counterattack lands
+const DRAGON_HIT_CHANCE: u32 = 75;
 
-    pub fn eval_strike_dragon(&mut self, game: GameExpr) -> Stage {
-        let (verb, noun, adverb, adj) = match game {
-            GameExpr::Svn { verb, noun, adverb, adj } => (verb, noun, adverb, adj),
-            _ => unreachable!(),
+impl Game {
+    pub fn eval_strike_dragon(&mut self, game: GameExpr) -> Result<Stage> {
+        let (verb, noun) = match game {
+            GameExpr::Svn { verb, noun, .. } => (verb, noun),
+            _ => return Err(GameErr::UnexpectedInput),
         };
 
-        match (verb, noun) {
+        Ok(match (verb, noun) {
             (VerbKind::Strike, NounKind::Sword) => {
                 if self.state.equipment.contains(&Equipment::Sword) {
                     msg("You strike the dragon with a great sword...\n");
-                    msg("It kills the dragon...\n");
+                    msg("It *kills the dragon*...\n");
+                    self.discover(Discovery::SlayDragon);
+                    self.award_finish_bonus();
                     Stage::Finish
                 } else {
                     msg("You do not have a sword!\n");
-                    msg("The dragon attacks you!\n");
-                    self.do_damage(4);
+                    if self.rng.chance(DRAGON_HIT_CHANCE) {
+                        msg("The {red}*dragon*{/} attacks you and {red}draws blood{/}!\n");
+                        self.do_damage(DamageKind::Physical, DRAGON_DAMAGE);
+                        self.inflict(StatusKind::Bleeding, 2);
+                    } else {
+                        msg("The {red}*dragon*{/} lunges at you, but you roll out of the way!\n");
+                    }
                     self.stage
                 }
             }
@@ -36,7 +44,6 @@ impl Game {
                 oops();
                 self.stage
             }
-        }
+        })
     }
 }
-
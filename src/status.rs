@@ -0,0 +1,157 @@
+use crate::ui::print;
+
+use crate::damage::DamageKind;
+use crate::game::{msg, Equipment, Game};
+use crate::lexer::{NounKind, VerbKind};
+use crate::score::Discovery;
+use crate::stage::{Stage, State};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusKind {
+    Frozen,
+    Burning,
+    Soaked,
+    Bleeding,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub turns_left: u32,
+}
+
+impl StatusKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatusKind::Frozen => "frozen",
+            StatusKind::Burning => "burning",
+            StatusKind::Soaked => "soaked",
+            StatusKind::Bleeding => "bleeding",
+        }
+    }
+
+    /// Damage taken at the start of every turn while the effect lasts
+    fn tick_damage(&self) -> Option<(DamageKind, u32)> {
+        match self {
+            StatusKind::Frozen => Some((DamageKind::Cold, 1)),
+            StatusKind::Burning => Some((DamageKind::Fire, 2)),
+            StatusKind::Bleeding => Some((DamageKind::Physical, 1)),
+            StatusKind::Soaked => None,
+        }
+    }
+
+    /// How much resistance to `kind` damage this effect strips away
+    pub fn resistance_penalty(&self, kind: DamageKind) -> u32 {
+        match (self, kind) {
+            (StatusKind::Soaked, DamageKind::Cold) => 60,
+            (StatusKind::Frozen, DamageKind::Physical) => 10,
+            _ => 0,
+        }
+    }
+
+    pub fn cure_hint(&self) -> &'static str {
+        match self {
+            StatusKind::Frozen => "keep moving; it will wear off",
+            StatusKind::Burning => "try rolling in the snow",
+            StatusKind::Soaked => "try removing your wet coat",
+            StatusKind::Bleeding => "it will stop on its own, eventually",
+        }
+    }
+}
+
+impl State {
+    pub fn has_status(&self, kind: StatusKind) -> bool {
+        self.status.iter().any(|effect| effect.kind == kind)
+    }
+}
+
+/// Stages where there is snow to roll around in
+fn is_outdoors(stage: Stage) -> bool {
+    matches!(
+        stage,
+        Stage::OutsideLibrary | Stage::BusArrive | Stage::CampusDragon | Stage::StrikeDragon
+    )
+}
+
+impl Game {
+    /// Applies `kind` for `turns` turns, extending it if already active
+    pub fn inflict(&mut self, kind: StatusKind, turns: u32) {
+        match self.state.status.iter_mut().find(|effect| effect.kind == kind) {
+            Some(effect) => effect.turns_left = effect.turns_left.max(turns),
+            None => self.state.status.push(StatusEffect { kind, turns_left: turns }),
+        }
+    }
+
+    pub fn cure(&mut self, kind: StatusKind) -> bool {
+        let before = self.state.status.len();
+        self.state.status.retain(|effect| effect.kind != kind);
+        before != self.state.status.len()
+    }
+
+    /// Deals damage for every active effect and expires the ones that ran out
+    pub fn tick_status(&mut self) {
+        for i in 0..self.state.status.len() {
+            let effect = self.state.status[i];
+            if let Some((damage_kind, n)) = effect.kind.tick_damage() {
+                let damage = self.do_damage(damage_kind, n);
+                // Resistances can shrink a tick to nothing; no need to say so
+                if damage > 0 {
+                    msg(&format!("You are {} and take {damage} damage...\n", effect.kind.name()));
+                }
+            }
+            self.state.status[i].turns_left -= 1;
+        }
+
+        let expired: Vec<StatusKind> = self
+            .state
+            .status
+            .iter()
+            .filter(|effect| effect.turns_left == 0)
+            .map(|effect| effect.kind)
+            .collect();
+        for kind in expired {
+            self.cure(kind);
+            msg(&format!("You are no longer {}\n", kind.name()));
+        }
+    }
+
+    /// Handles actions that cure effects in any stage; returns whether the
+    /// action was used up
+    pub fn eval_cure(&mut self, verb: VerbKind, noun: NounKind) -> bool {
+        match (verb, noun) {
+            (VerbKind::Roll, NounKind::Snow) if is_outdoors(self.stage) => {
+                if self.cure(StatusKind::Burning) {
+                    msg("You roll around in the snow and put out the {red}flames{/}...\n");
+                    self.discover(Discovery::StopDropAndRoll);
+                } else {
+                    msg("You roll around in the snow for no reason in particular...\n");
+                }
+                msg("Your clothes are soaked through...\n");
+                self.inflict(StatusKind::Soaked, 6);
+                true
+            }
+            (VerbKind::Remove, NounKind::Coat) => {
+                if !self.state.equipment.remove(&Equipment::Coat) {
+                    msg("You are not wearing a coat\n");
+                } else if self.cure(StatusKind::Soaked) {
+                    msg("You peel off the soaking wet coat and toss it aside...\n");
+                } else {
+                    msg("You take off your coat\n");
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn print_status_effects(&self) {
+        for effect in &self.state.status {
+            print(&format!(
+                "You are {} ({} turns left): {}\n",
+                effect.kind.name(),
+                effect.turns_left,
+                effect.kind.cure_hint()
+            ));
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Templates for story text, filled in from the player's state
+//!
+//! - `{name}`, `{time_left}`, `{pronoun.subject}` insert a value; `{Name}`
+//!   capitalizes it
+//! - `{time_left|minute|minutes}` inserts a number and the matching noun
+//! - `{if has coat}...{else}...{/if}` picks text by a condition; `not`
+//!   negates it
+//! - anything else in braces, like markup colors, is left alone
+
+use crate::clock::Clock;
+use crate::game::{Equipment, Game};
+use crate::markup;
+use crate::stage::State;
+use crate::status::StatusKind;
+use crate::ui::print;
+
+#[derive(Debug)]
+enum Node<'a> {
+    Text(&'a str),
+    Var(&'a str),
+    If {
+        condition: &'a str,
+        then: Vec<Node<'a>>,
+        otherwise: Vec<Node<'a>>,
+    },
+}
+
+/// Parses nodes up to the end of `src` or an `{else}` / `{/if}`, which is
+/// returned with the nodes
+fn parse<'a>(src: &mut &'a str) -> (Vec<Node<'a>>, Option<&'a str>) {
+    let mut nodes = Vec::new();
+    while !src.is_empty() {
+        let Some(open) = find_open(src) else {
+            nodes.push(Node::Text(src));
+            *src = "";
+            break;
+        };
+        if open > 0 {
+            nodes.push(Node::Text(&src[..open]));
+        }
+        let Some(close) = src[open..].find('}').map(|close| open + close) else {
+            nodes.push(Node::Text(&src[open..]));
+            *src = "";
+            break;
+        };
+        let tag = &src[open + 1..close];
+        let whole = &src[open..=close];
+        *src = &src[close + 1..];
+
+        if let Some(condition) = tag.strip_prefix("if ") {
+            let (then, end) = parse(src);
+            let otherwise = if end == Some("else") { parse(src).0 } else { Vec::new() };
+            nodes.push(Node::If {
+                condition: condition.trim(),
+                then,
+                otherwise,
+            });
+        } else if tag == "else" || tag == "/if" {
+            return (nodes, Some(tag));
+        } else if is_variable(tag) {
+            nodes.push(Node::Var(tag));
+        } else {
+            nodes.push(Node::Text(whole));
+        }
+    }
+    (nodes, None)
+}
+
+/// Position of the next unescaped `{`
+fn find_open(src: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in src.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '{' if !escaped => return Some(i),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+fn is_variable(tag: &str) -> bool {
+    let name = tag.split('|').next().unwrap_or(tag).to_ascii_lowercase();
+    matches!(
+        name.as_str(),
+        "name"
+            | "health"
+            | "time_left"
+            | "clock"
+            | "class_time"
+            | "bus_arrival"
+            | "background"
+            | "pronoun.subject"
+            | "pronoun.object"
+            | "pronoun.possessive"
+    )
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn variable(tag: &str, state: &State) -> String {
+    let mut parts = tag.split('|');
+    let name = parts.next().unwrap_or(tag);
+    let value = match name.to_ascii_lowercase().as_str() {
+        "name" => markup::escape(&state.name),
+        "health" => state.health.to_string(),
+        "time_left" => state.time_left().to_string(),
+        "clock" => state.clock.to_string(),
+        "class_time" => state.class_time.to_string(),
+        "bus_arrival" => state.bus_arrived_at.map(|at| at.to_string()).unwrap_or_default(),
+        "background" => state.background.name().to_string(),
+        "pronoun.subject" => state.pronouns.subject().to_string(),
+        "pronoun.object" => state.pronouns.object().to_string(),
+        "pronoun.possessive" => state.pronouns.possessive().to_string(),
+        _ => String::new(),
+    };
+    let value = match (parts.next(), parts.next()) {
+        (Some(singular), Some(plural)) => {
+            let noun = if value == "1" { singular } else { plural };
+            format!("{value} {noun}")
+        }
+        _ => value,
+    };
+    if name.starts_with(char::is_uppercase) {
+        capitalize(&value)
+    } else {
+        value
+    }
+}
+
+fn condition(condition: &str, state: &State) -> bool {
+    if let Some(condition) = condition.strip_prefix("not ") {
+        return !self::condition(condition.trim(), state);
+    }
+    let mut words = condition.split_whitespace();
+    match (words.next(), words.next()) {
+        (Some("has"), Some("coat")) => state.equipment.contains(&Equipment::Coat),
+        (Some("has"), Some("sword")) => state.equipment.contains(&Equipment::Sword),
+        (Some("bus"), None) => state.bus_at_stop,
+        (Some("before"), Some(time)) => {
+            let Some((hour, minute)) = time.split_once(':') else {
+                return false;
+            };
+            match (hour.parse(), minute.parse()) {
+                (Ok(hour), Ok(minute)) => state.clock < Clock::at(hour, minute),
+                _ => false,
+            }
+        }
+        (Some("frozen"), None) => state.has_status(StatusKind::Frozen),
+        (Some("burning"), None) => state.has_status(StatusKind::Burning),
+        (Some("soaked"), None) => state.has_status(StatusKind::Soaked),
+        (Some("bleeding"), None) => state.has_status(StatusKind::Bleeding),
+        _ => false,
+    }
+}
+
+fn render_nodes(nodes: &[Node], state: &State, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Var(tag) => out.push_str(&variable(tag, state)),
+            Node::If {
+                condition: test,
+                then,
+                otherwise,
+            } => {
+                let branch = if condition(test, state) { then } else { otherwise };
+                render_nodes(branch, state, out);
+            }
+        }
+    }
+}
+
+/// Fills in `template` from `state`, leaving markup untouched
+pub fn render(template: &str, state: &State) -> String {
+    let mut src = template;
+    let mut nodes = Vec::new();
+    // A stray `{else}` or `{/if}` is dropped rather than ending the text
+    while !src.is_empty() {
+        nodes.extend(parse(&mut src).0);
+    }
+    let mut out = String::new();
+    render_nodes(&nodes, state, &mut out);
+    out
+}
+
+impl Game {
+    /// Prints story text after rendering it as a template
+    pub fn narrate(&self, template: &str) {
+        print(&render(template, &self.state));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> State {
+        State {
+            name: "bob".to_string(),
+            ..State::default()
+        }
+    }
+
+    #[test]
+    fn nested_if_and_else() {
+        let template = "{if has coat}{if has sword}both{else}coat{/if}{else}nothing{/if}";
+        let mut state = state();
+        assert_eq!(render(template, &state), "nothing");
+        state.equipment.insert(Equipment::Coat);
+        assert_eq!(render(template, &state), "coat");
+        state.equipment.insert(Equipment::Sword);
+        assert_eq!(render(template, &state), "both");
+        assert_eq!(render("{if not has coat}cold{/if}", &state), "");
+    }
+
+    #[test]
+    fn escaped_braces_are_left_for_markup() {
+        let rendered = render("\\{name} is {name}", &state());
+        assert_eq!(rendered, "\\{name} is bob");
+        assert_eq!(markup::strip(&rendered), "{name} is bob");
+    }
+
+    #[test]
+    fn plurals_follow_the_value() {
+        let mut state = state();
+        state.health = 1;
+        assert_eq!(render("{health|point|points}", &state), "1 point");
+        state.health = 2;
+        assert_eq!(render("{health|point|points}", &state), "2 points");
+    }
+
+    #[test]
+    fn capitalized_variables() {
+        assert_eq!(render("{Name}, {name}", &state()), "Bob, bob");
+        assert_eq!(render("{Pronoun.subject} left", &state()), "They left");
+    }
+
+    #[test]
+    fn unknown_tags_pass_through() {
+        assert_eq!(render("{red}hot{/} {nope}", &state()), "{red}hot{/} {nope}");
+    }
+}
@@ -0,0 +1,304 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::markup::{self, Cell, Color, Style};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Markdown,
+    Html,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "text" | "txt" => Some(Format::Text),
+            "markdown" | "md" => Some(Format::Markdown),
+            "html" | "htm" => Some(Format::Html),
+            _ => None,
+        }
+    }
+
+    /// Guesses the format from a file extension, defaulting to plain text
+    pub fn from_path(path: &Path) -> Self {
+        path.extension()
+            .and_then(|ext| Self::from_name(&ext.to_string_lossy().to_lowercase()))
+            .unwrap_or(Format::Text)
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            Format::Text => "txt",
+            Format::Markdown => "md",
+            Format::Html => "html",
+        }
+    }
+}
+
+struct Transcript {
+    out: BufWriter<File>,
+    format: Format,
+    /// Output that has not been terminated by a newline yet
+    pending: String,
+}
+
+thread_local! {
+    static TRANSCRIPT: RefCell<Option<Transcript>> = const { RefCell::new(None) };
+}
+
+const HTML_HEADER: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Batman transcript</title>
+<style>
+body { background: #111; color: #ddd; }
+.input { color: #8f8; }
+.prompt { color: #888; font-style: italic; }
+</style>
+</head>
+<body>
+<pre>
+"#;
+
+const HTML_FOOTER: &str = "</pre>\n</body>\n</html>\n";
+
+pub(crate) fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Formats seconds since the epoch as an ISO 8601 UTC timestamp
+pub(crate) fn timestamp(secs: u64) -> String {
+    let days = (secs / 86400) as i64;
+    let rem = secs % 86400;
+
+    // Howard Hinnant's days-to-civil algorithm
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        rem / 3600,
+        rem / 60 % 60,
+        rem % 60
+    )
+}
+
+/// Default transcript file name, e.g. `batman-1705392300.md`
+pub fn default_path(format: Format) -> PathBuf {
+    PathBuf::from(format!("batman-{}.{}", unix_time(), format.extension()))
+}
+
+pub fn is_recording() -> bool {
+    TRANSCRIPT.with(|transcript| transcript.borrow().is_some())
+}
+
+/// Starts writing everything shown and typed to `path`, replacing any
+/// transcript already being written
+pub fn start(path: &Path, format: Format) -> io::Result<()> {
+    stop();
+    let mut transcript = Transcript {
+        out: BufWriter::new(File::create(path)?),
+        format,
+        pending: String::new(),
+    };
+    let started = format!("Transcript started {}", timestamp(unix_time()));
+    match format {
+        Format::Text => writeln!(transcript.out, "{started}\n")?,
+        Format::Markdown => writeln!(transcript.out, "# Batman\n\n_{started}_\n")?,
+        Format::Html => write!(transcript.out, "{HTML_HEADER}<i>{started}</i>\n\n")?,
+    }
+    TRANSCRIPT.with(|cell| *cell.borrow_mut() = Some(transcript));
+    Ok(())
+}
+
+pub fn stop() {
+    TRANSCRIPT.with(|cell| {
+        if let Some(mut transcript) = cell.borrow_mut().take() {
+            transcript.flush_pending();
+            let ended = format!("Transcript ended {}", timestamp(unix_time()));
+            let _ = match transcript.format {
+                Format::Text => writeln!(transcript.out, "\n{ended}"),
+                Format::Markdown => writeln!(transcript.out, "\n_{ended}_"),
+                Format::Html => write!(transcript.out, "\n<i>{ended}</i>\n{HTML_FOOTER}"),
+            };
+            let _ = transcript.out.flush();
+        }
+    });
+}
+
+fn with_transcript(f: impl FnOnce(&mut Transcript) -> io::Result<()>) {
+    TRANSCRIPT.with(|cell| {
+        if let Some(transcript) = cell.borrow_mut().as_mut() {
+            // A transcript is a nicety; failing to write one should never
+            // interrupt the game
+            let _ = f(transcript).and_then(|_| transcript.out.flush());
+        }
+    });
+}
+
+/// Records story text, which may contain markup
+pub fn output(s: &str) {
+    with_transcript(|transcript| {
+        transcript.pending.push_str(s);
+        while let Some(i) = transcript.pending.find('\n') {
+            let line: String = transcript.pending.drain(..=i).collect();
+            transcript.write_line(line.trim_end_matches('\n'))?;
+        }
+        Ok(())
+    });
+}
+
+/// Records a line typed by the player
+pub fn input(s: &str) {
+    with_transcript(|transcript| {
+        transcript.flush_pending();
+        match transcript.format {
+            Format::Text => writeln!(transcript.out, "> {s}"),
+            Format::Markdown => writeln!(transcript.out, "**> {}**  ", markdown_escape(s)),
+            Format::Html => {
+                writeln!(transcript.out, "<span class=\"input\">&gt; {}</span>", html_escape(s))
+            }
+        }
+    });
+}
+
+/// Records a prompt such as "Press any key to continue..."
+pub fn prompt(s: &str) {
+    with_transcript(|transcript| {
+        transcript.flush_pending();
+        match transcript.format {
+            Format::Text => writeln!(transcript.out, "[{s}]"),
+            Format::Markdown => writeln!(transcript.out, "_{}_  ", markdown_escape(s)),
+            Format::Html => {
+                writeln!(transcript.out, "<span class=\"prompt\">{}</span>", html_escape(s))
+            }
+        }
+    });
+}
+
+impl Transcript {
+    fn flush_pending(&mut self) {
+        if !self.pending.is_empty() {
+            let line = std::mem::take(&mut self.pending);
+            let _ = self.write_line(&line);
+        }
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        match self.format {
+            Format::Text => writeln!(self.out, "{}", markup::strip(line)),
+            Format::Markdown => writeln!(self.out, "{}  ", markdown_line(&markup::parse(line))),
+            Format::Html => writeln!(self.out, "{}", html_line(&markup::parse(line))),
+        }
+    }
+}
+
+/// Consecutive characters sharing the same style
+fn runs(cells: &[Cell]) -> Vec<(Style, String)> {
+    let mut runs: Vec<(Style, String)> = Vec::new();
+    for &(c, style) in cells {
+        match runs.last_mut() {
+            Some((last, text)) if *last == style => text.push(c),
+            _ => runs.push((style, c.to_string())),
+        }
+    }
+    runs
+}
+
+fn markdown_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '\\' | '*' | '_' | '[' | ']' | '`' | '<' | '>' | '#') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn markdown_line(cells: &[Cell]) -> String {
+    let mut line = String::new();
+    for (style, text) in runs(cells) {
+        let trimmed = text.trim();
+        if trimmed.is_empty() || !(style.bold || style.highlight) {
+            line.push_str(&markdown_escape(&text));
+            continue;
+        }
+        // Emphasis markers must hug the text they wrap
+        let lead = &text[..text.len() - text.trim_start().len()];
+        let trail = &text[text.trim_end().len()..];
+        let marker = if style.bold { "**" } else { "_" };
+        line.push_str(&format!("{lead}{marker}{}{marker}{trail}", markdown_escape(trimmed)));
+    }
+    line
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn html_line(cells: &[Cell]) -> String {
+    let mut line = String::new();
+    for (style, text) in runs(cells) {
+        let mut text = html_escape(&text);
+        if let Some(color) = style.color.or(style.highlight.then_some(Color::Cyan)) {
+            text = format!("<span style=\"color: {}\">{text}</span>", color.name());
+        }
+        if style.highlight {
+            text = format!("<u>{text}</u>");
+        }
+        if style.bold || style.highlight {
+            text = format!("<b>{text}</b>");
+        }
+        line.push_str(&text);
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markup;
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(timestamp(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(timestamp(1709210096), "2024-02-29 12:34:56 UTC");
+        assert_eq!(timestamp(951868799), "2000-02-29 23:59:59 UTC");
+        assert_eq!(timestamp(1262217600), "2009-12-31 00:00:00 UTC");
+    }
+
+    #[test]
+    fn markdown_escapes_special_characters() {
+        assert_eq!(markdown_escape("a*b_c#d\\e"), "a\\*b\\_c\\#d\\\\e");
+        assert_eq!(markdown_escape("plain text"), "plain text");
+    }
+
+    #[test]
+    fn markdown_emphasis_hugs_the_text() {
+        assert_eq!(markdown_line(&markup::parse("a* bold *b")), "a **bold** b");
+        assert_eq!(markdown_line(&markup::parse("[ coat ]")), " _coat_ ");
+        assert_eq!(markdown_line(&markup::parse("* *")), " ");
+    }
+
+    #[test]
+    fn html_escapes_special_characters() {
+        assert_eq!(html_escape("a & b < c > d"), "a &amp; b &lt; c &gt; d");
+        assert_eq!(html_escape("&lt;"), "&amp;lt;");
+    }
+}
@@ -0,0 +1,555 @@
+use std::cell::RefCell;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::time::Duration;
+
+use ncurses::*;
+
+use crate::markup::{self, Cell, Color, Style};
+use crate::transcript;
+
+/// Width of the sidebar pane, when the terminal is wide enough to fit it
+const SIDEBAR_WIDTH: i32 = 28;
+const MIN_COLS_FOR_SIDEBAR: i32 = 72;
+
+enum Frontend {
+    Curses(Curses),
+    /// Plain text on stdout, for dumb terminals and piped input
+    Plain,
+}
+
+struct Curses {
+    status: WINDOW,
+    transcript: WINDOW,
+    sidebar: Option<WINDOW>,
+    input: WINDOW,
+    /// Everything printed this session; the last line may still be growing
+    lines: Vec<Vec<Cell>>,
+    /// How many rows the transcript is scrolled back from the bottom
+    scroll: usize,
+    status_line: String,
+    sidebar_lines: Vec<String>,
+    typewriter: Option<Typewriter>,
+    /// Whether `wait_key` actually waits; off while replaying a recording
+    pauses: bool,
+}
+
+/// Reveals story text gradually, for dramatic effect
+#[derive(Debug, Clone, Copy)]
+pub struct Typewriter {
+    pub char_delay: Duration,
+    pub line_pause: Duration,
+}
+
+thread_local! {
+    static FRONTEND: RefCell<Option<Frontend>> = const { RefCell::new(None) };
+    static MUTED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    /// Set once stdout is gone, e.g. when piped into `head`
+    static CLOSED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+fn with_frontend<T>(f: impl FnOnce(&mut Frontend) -> T) -> Option<T> {
+    FRONTEND.with(|frontend| frontend.borrow_mut().as_mut().map(f))
+}
+
+/// Runs `f` against the curses frontend, if that is the one in use
+fn with_ui<T>(f: impl FnOnce(&mut Curses) -> T) -> Option<T> {
+    with_frontend(|frontend| match frontend {
+        Frontend::Curses(ui) => Some(f(ui)),
+        Frontend::Plain => None,
+    })
+    .flatten()
+}
+
+pub fn is_plain() -> bool {
+    with_frontend(|frontend| matches!(frontend, Frontend::Plain)).unwrap_or(true)
+}
+
+pub fn init_plain() {
+    FRONTEND.with(|cell| *cell.borrow_mut() = Some(Frontend::Plain));
+}
+
+pub fn init() {
+    setlocale(LcCategory::all, "");
+    initscr();
+    cbreak();
+    noecho();
+    if has_colors() {
+        start_color();
+        use_default_colors();
+        for color in Color::ALL {
+            init_pair(color_pair(color), curses_color(color), -1);
+        }
+    }
+
+    let ui = Curses {
+        status: newwin(1, 1, 0, 0),
+        transcript: newwin(1, 1, 0, 0),
+        sidebar: None,
+        input: newwin(1, 1, 0, 0),
+        lines: vec![Vec::new()],
+        scroll: 0,
+        status_line: String::new(),
+        sidebar_lines: Vec::new(),
+        typewriter: None,
+        pauses: true,
+    };
+    FRONTEND.with(|cell| *cell.borrow_mut() = Some(Frontend::Curses(ui)));
+    with_ui(|ui| {
+        ui.layout();
+        ui.render();
+    });
+}
+
+pub fn end() {
+    FRONTEND.with(|cell| {
+        if let Some(Frontend::Curses(ui)) = cell.borrow_mut().take() {
+            ui.delete_windows();
+            endwin();
+        }
+    });
+}
+
+/// Leaves curses mode without touching the frontend state, which may be
+/// mid-update when a panic strikes
+pub fn restore_terminal() {
+    let curses = FRONTEND
+        .try_with(|cell| {
+            cell.try_borrow()
+                .map(|frontend| matches!(*frontend, Some(Frontend::Curses(_))))
+                .unwrap_or(true)
+        })
+        .unwrap_or(false);
+    if curses {
+        endwin();
+    }
+}
+
+/// Enables the typewriter effect; it has no effect on the plain frontend
+pub fn set_typewriter(typewriter: Option<Typewriter>) {
+    with_ui(|ui| ui.typewriter = typewriter);
+}
+
+pub fn set_pauses(pauses: bool) {
+    with_ui(|ui| ui.pauses = pauses);
+}
+
+/// Silences all output and pauses, e.g. while a saved run is replayed
+pub fn set_muted(muted: bool) {
+    MUTED.with(|cell| cell.set(muted));
+}
+
+fn is_muted() -> bool {
+    MUTED.with(|cell| cell.get())
+}
+
+/// Whether the plain frontend lost its stdout; the game should stop then
+pub fn is_closed() -> bool {
+    CLOSED.with(|cell| cell.get())
+}
+
+/// Writes to stdout for the plain frontend, noting a closed pipe instead of
+/// panicking like `print!` would
+fn write_plain(s: &str) {
+    if is_closed() {
+        return;
+    }
+    let mut stdout = io::stdout().lock();
+    if let Err(e) = stdout.write_all(s.as_bytes()).and_then(|()| stdout.flush()) {
+        if e.kind() == io::ErrorKind::BrokenPipe {
+            CLOSED.with(|cell| cell.set(true));
+        }
+    }
+}
+
+/// Appends story text, which may contain markup, to the transcript
+pub fn print(s: &str) {
+    if is_muted() {
+        return;
+    }
+    transcript::output(s);
+    with_frontend(|frontend| match frontend {
+        Frontend::Curses(ui) => {
+            let cells = markup::parse(s);
+            match ui.typewriter {
+                Some(typewriter) => ui.type_out(&cells, typewriter),
+                None => ui.append(&cells),
+            }
+        }
+        Frontend::Plain => write_plain(&markup::strip(s)),
+    });
+}
+
+/// Starts a new scene, leaving a blank line after the previous one
+pub fn separator() {
+    if is_muted() {
+        return;
+    }
+    if with_frontend(|frontend| matches!(frontend, Frontend::Plain)).unwrap_or(false) {
+        write_plain("\n");
+        transcript::output("\n");
+        return;
+    }
+    with_ui(|ui| {
+        let len = ui.lines.len();
+        if len < 2 || !ui.lines[len - 2].is_empty() || !ui.lines[len - 1].is_empty() {
+            ui.lines.push(Vec::new());
+            transcript::output("\n");
+        }
+    });
+}
+
+pub fn set_status(line: &str, sidebar: Vec<String>) {
+    with_ui(|ui| {
+        ui.status_line = line.to_string();
+        ui.sidebar_lines = sidebar;
+        ui.render_status();
+        ui.render_sidebar();
+        doupdate();
+    });
+}
+
+/// Shows `prompt` on the input line and waits for a key; the transcript can
+/// still be scrolled while waiting
+pub fn wait_key(prompt: &str) {
+    if is_muted() {
+        return;
+    }
+    transcript::prompt(prompt);
+    let Some(input) = with_ui(|ui| {
+        ui.render_input(prompt);
+        ui.pauses.then_some(ui.input)
+    })
+    .flatten() else {
+        return;
+    };
+    loop {
+        let key = wgetch(input);
+        if key == KEY_RESIZE {
+            with_ui(|ui| {
+                ui.resize();
+                ui.render_input(prompt);
+            });
+            continue;
+        }
+        if !with_ui(|ui| ui.scroll_key(key)).unwrap_or(false) {
+            break;
+        }
+    }
+}
+
+/// Reads a line of player input and echoes it into the transcript; `None`
+/// once there is no more input
+pub fn read_line() -> Option<String> {
+    if is_plain() {
+        return read_line_plain();
+    }
+
+    let mut s = String::new();
+    loop {
+        let input = with_ui(|ui| {
+            ui.render_input(&format!("> {s}"));
+            ui.input
+        })?;
+        let key = wgetch(input);
+        if key == KEY_RESIZE {
+            with_ui(|ui| ui.resize());
+            continue;
+        }
+        if with_ui(|ui| ui.scroll_key(key)).unwrap_or(false) {
+            continue;
+        }
+        match key {
+            KEY_ENTER | 10 | 13 => break,
+            KEY_BACKSPACE | 127 | 8 => {
+                s.pop();
+            }
+            c if (32..127).contains(&c) => s.push(c as u8 as char),
+            _ => (),
+        }
+    }
+    echo_input(&s);
+    Some(s)
+}
+
+/// Shows a line of player input in the transcript, as if it had been typed
+pub fn echo_input(s: &str) {
+    with_frontend(|frontend| match frontend {
+        Frontend::Curses(ui) => ui.append(&markup::parse(&format!("> {}\n", markup::escape(s)))),
+        Frontend::Plain => write_plain(&format!("> {s}\n")),
+    });
+    transcript::input(s);
+}
+
+fn read_line_plain() -> Option<String> {
+    write_plain("> ");
+    if is_closed() {
+        return None;
+    }
+    let mut s = String::new();
+    if io::stdin().lock().read_line(&mut s).ok()? == 0 {
+        write_plain("\n");
+        return None;
+    }
+    let s = s.trim_end_matches(['\n', '\r']).to_string();
+    // Piped input is not echoed by the terminal
+    if !io::stdin().is_terminal() {
+        write_plain(&format!("{s}\n"));
+    }
+    transcript::input(&s);
+    Some(s)
+}
+
+fn color_pair(color: Color) -> i16 {
+    Color::ALL.iter().position(|&c| c == color).unwrap() as i16 + 1
+}
+
+fn curses_color(color: Color) -> i16 {
+    match color {
+        Color::Red => COLOR_RED,
+        Color::Yellow => COLOR_YELLOW,
+        Color::Green => COLOR_GREEN,
+        Color::Cyan => COLOR_CYAN,
+        Color::Blue => COLOR_BLUE,
+        Color::Magenta => COLOR_MAGENTA,
+    }
+}
+
+fn attributes(style: Style) -> attr_t {
+    let mut attr = A_NORMAL();
+    if style.bold {
+        attr |= A_BOLD();
+    }
+    if style.highlight {
+        attr |= A_UNDERLINE() | A_BOLD();
+    }
+    if let Some(color) = style.color.or(style.highlight.then_some(Color::Cyan)) {
+        if has_colors() {
+            attr |= COLOR_PAIR(color_pair(color));
+        }
+    }
+    attr
+}
+
+/// Breaks `line` into rows of at most `width` characters, preferring to break
+/// between words
+fn wrap(line: &[Cell], width: usize) -> Vec<Vec<Cell>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut row_len = 0;
+
+    for word in line.split(|&(c, _)| c == ' ') {
+        let mut word = word.to_vec();
+        if row_len > 0 && row_len + 1 + word.len() > width {
+            rows.push(std::mem::take(&mut row));
+            row_len = 0;
+        }
+        if row_len > 0 {
+            row.push((' ', Style::default()));
+            row_len += 1;
+        }
+        // Words that do not fit on a row of their own are split
+        while row_len + word.len() > width {
+            let rest = word.split_off(width - row_len);
+            row.extend(word);
+            rows.push(std::mem::take(&mut row));
+            row_len = 0;
+            word = rest;
+        }
+        row_len += word.len();
+        row.extend(word);
+    }
+    rows.push(row);
+    rows
+}
+
+impl Curses {
+    fn delete_windows(&self) {
+        delwin(self.status);
+        delwin(self.transcript);
+        delwin(self.input);
+        if let Some(sidebar) = self.sidebar {
+            delwin(sidebar);
+        }
+    }
+
+    /// (Re)creates every pane to fit the current terminal size
+    fn layout(&mut self) {
+        self.delete_windows();
+
+        let rows = LINES().max(3);
+        let cols = COLS().max(1);
+        let sidebar_width = if cols >= MIN_COLS_FOR_SIDEBAR { SIDEBAR_WIDTH } else { 0 };
+
+        self.status = newwin(1, cols, 0, 0);
+        self.transcript = newwin(rows - 2, cols - sidebar_width, 1, 0);
+        self.sidebar = (sidebar_width > 0)
+            .then(|| newwin(rows - 2, sidebar_width, 1, cols - sidebar_width));
+        self.input = newwin(1, cols, rows - 1, 0);
+        keypad(self.input, true);
+    }
+
+    fn push(&mut self, cell: Cell) {
+        match cell {
+            ('\n', _) => self.lines.push(Vec::new()),
+            cell => self.lines.last_mut().unwrap().push(cell),
+        }
+    }
+
+    fn append(&mut self, cells: &[Cell]) {
+        for &cell in cells {
+            self.push(cell);
+        }
+        self.scroll = 0;
+        self.render_transcript();
+        doupdate();
+    }
+
+    /// Appends `cells` one at a time, pausing between them; any key skips to
+    /// the end
+    fn type_out(&mut self, cells: &[Cell], typewriter: Typewriter) {
+        self.scroll = 0;
+        for (i, &cell) in cells.iter().enumerate() {
+            self.push(cell);
+            let delay = if cell.0 == '\n' {
+                typewriter.line_pause
+            } else {
+                typewriter.char_delay
+            };
+            if delay.is_zero() || cell.0 == ' ' {
+                continue;
+            }
+            self.render_transcript();
+            doupdate();
+
+            wtimeout(self.input, delay.as_millis() as i32);
+            let key = wgetch(self.input);
+            wtimeout(self.input, -1);
+            if key == KEY_RESIZE {
+                self.resize();
+            } else if key != ERR {
+                self.append(&cells[i + 1..]);
+                return;
+            }
+        }
+        self.render_transcript();
+        doupdate();
+    }
+
+    /// Lays the panes out again after the terminal changed size and reflows
+    /// the transcript to the new width
+    fn resize(&mut self) {
+        self.layout();
+        self.render();
+    }
+
+    fn render(&mut self) {
+        self.render_status();
+        self.render_transcript();
+        self.render_sidebar();
+        self.render_input("");
+        doupdate();
+    }
+
+    /// Transcript lines broken into rows that fit the transcript pane
+    fn rows(&self) -> Vec<Vec<Cell>> {
+        let width = getmaxx(self.transcript).max(1) as usize;
+        self.lines.iter().flat_map(|line| wrap(line, width)).collect()
+    }
+
+    fn render_transcript(&mut self) {
+        let height = getmaxy(self.transcript).max(1) as usize;
+        let rows = self.rows();
+        self.scroll = self.scroll.min(rows.len().saturating_sub(height));
+        let end = rows.len() - self.scroll;
+        let start = end.saturating_sub(height);
+
+        werase(self.transcript);
+        for (y, row) in rows[start..end].iter().enumerate() {
+            wmove(self.transcript, y as i32, 0);
+            for &(c, style) in row {
+                wattrset(self.transcript, attributes(style));
+                waddstr(self.transcript, c.encode_utf8(&mut [0; 4]));
+            }
+        }
+        wattrset(self.transcript, A_NORMAL());
+        wnoutrefresh(self.transcript);
+    }
+
+    fn render_status(&self) {
+        let width = getmaxx(self.status).max(0) as usize;
+        let line: String = format!("{:width$}", self.status_line).chars().take(width).collect();
+        werase(self.status);
+        wattron(self.status, A_REVERSE());
+        mvwaddstr(self.status, 0, 0, &line);
+        wattroff(self.status, A_REVERSE());
+        wnoutrefresh(self.status);
+    }
+
+    fn render_sidebar(&self) {
+        let Some(sidebar) = self.sidebar else {
+            return;
+        };
+        let height = getmaxy(sidebar);
+        let width = (getmaxx(sidebar) - 2).max(0) as usize;
+        werase(sidebar);
+        mvwvline(sidebar, 0, 0, ACS_VLINE(), height);
+        for (y, line) in self.sidebar_lines.iter().take(height as usize).enumerate() {
+            let line: String = line.chars().take(width).collect();
+            mvwaddstr(sidebar, y as i32, 2, &line);
+        }
+        wnoutrefresh(sidebar);
+    }
+
+    /// Draws `s` on the input line, showing only its tail when it is too
+    /// long so the cursor stays on screen
+    fn render_input(&self, s: &str) {
+        let width = getmaxx(self.input).max(1) as usize;
+        let len = s.chars().count();
+        let visible: String = s.chars().skip((len + 1).saturating_sub(width)).collect();
+        werase(self.input);
+        mvwaddstr(self.input, 0, 0, &visible);
+        wnoutrefresh(self.input);
+        doupdate();
+    }
+
+    /// Handles transcript scrolling keys; returns whether `key` was one
+    fn scroll_key(&mut self, key: i32) -> bool {
+        let page = (getmaxy(self.transcript) - 1).max(1) as usize;
+        match key {
+            KEY_PPAGE => self.scroll += page,
+            KEY_NPAGE => self.scroll = self.scroll.saturating_sub(page),
+            _ => return false,
+        }
+        self.render_transcript();
+        doupdate();
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(s: &str, width: usize) -> Vec<String> {
+        wrap(&markup::parse(s), width)
+            .iter()
+            .map(|row| row.iter().map(|&(c, _)| c).collect())
+            .collect()
+    }
+
+    #[test]
+    fn wraps_between_words() {
+        assert_eq!(rows("the quick brown fox", 10), ["the quick", "brown fox"]);
+    }
+
+    #[test]
+    fn splits_words_longer_than_the_width() {
+        assert_eq!(rows("abcdefghij", 4), ["abcd", "efgh", "ij"]);
+        assert_eq!(rows("ab cdefghij", 4), ["ab", "cdef", "ghij"]);
+    }
+
+    #[test]
+    fn empty_line_is_one_row() {
+        assert_eq!(rows("", 5), [""]);
+    }
+}